@@ -0,0 +1,145 @@
+//! Arithmetic in checked native integers, integers mod m and `BigUint`s behind one trait.
+//!
+//! An `Arith` value carries what the arithmetic needs, such as the modulus, so the same code
+//! computes exact, modular and big results.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::arith::{Arith, Checked, Modular};
+//!
+//! // the sum of 1 to n, in any arithmetic
+//! fn triangle<A: Arith>(ar: &A, n: u64) -> Option<A::T> {
+//!     (1..n + 1).try_fold(ar.zero(), |acc, k| ar.add(&acc, &ar.scale(&ar.one(), k)?))
+//! }
+//!
+//! assert_eq!(triangle(&Checked::<u64>::new(), 100), Some(5050));
+//! assert_eq!(triangle(&Modular(7), 100), Some(5050 % 7));
+//! assert_eq!(triangle(&Checked::<u64>::new(), 10_000_000_000), None);
+//! ```
+
+extern crate num;
+use self::num::{BigUint, One, Zero};
+
+use std::marker::PhantomData;
+
+/// Arithmetic on values of type T, where None is an overflow.
+pub trait Arith {
+    type T: Clone;
+    fn zero(&self) -> Self::T;
+    fn one(&self) -> Self::T;
+    fn add(&self, a: &Self::T, b: &Self::T) -> Option<Self::T>;
+    /// Returns a - b, or None if it is negative outside modular arithmetic.
+    fn sub(&self, a: &Self::T, b: &Self::T) -> Option<Self::T>;
+    fn mul(&self, a: &Self::T, b: &Self::T) -> Option<Self::T>;
+    /// Returns a * k.
+    fn scale(&self, a: &Self::T, k: u64) -> Option<Self::T>;
+}
+
+/// Checked arithmetic on `u64` or `u128`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Checked<T>(PhantomData<T>);
+
+impl<T> Checked<T> {
+    pub fn new() -> Checked<T> {
+        Checked(PhantomData)
+    }
+}
+
+macro_rules! checked_impl {
+    ($($t:ty),*) => {$(
+        impl Arith for Checked<$t> {
+            type T = $t;
+
+            fn zero(&self) -> $t {
+                0
+            }
+
+            fn one(&self) -> $t {
+                1
+            }
+
+            fn add(&self, a: &$t, b: &$t) -> Option<$t> {
+                a.checked_add(*b)
+            }
+
+            fn sub(&self, a: &$t, b: &$t) -> Option<$t> {
+                a.checked_sub(*b)
+            }
+
+            fn mul(&self, a: &$t, b: &$t) -> Option<$t> {
+                a.checked_mul(*b)
+            }
+
+            fn scale(&self, a: &$t, k: u64) -> Option<$t> {
+                a.checked_mul(k as $t)
+            }
+        }
+    )*};
+}
+
+checked_impl!(u64, u128);
+
+/// Arithmetic on `u64`s mod m, which never overflows. The modulus must not be zero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Modular(pub u64);
+
+impl Arith for Modular {
+    type T = u64;
+
+    fn zero(&self) -> u64 {
+        0
+    }
+
+    fn one(&self) -> u64 {
+        1 % self.0
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> Option<u64> {
+        Some(((*a as u128 + *b as u128) % self.0 as u128) as u64)
+    }
+
+    fn sub(&self, a: &u64, b: &u64) -> Option<u64> {
+        Some(((*a as u128 + self.0 as u128 - *b as u128) % self.0 as u128) as u64)
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> Option<u64> {
+        Some((*a as u128 * *b as u128 % self.0 as u128) as u64)
+    }
+
+    fn scale(&self, a: &u64, k: u64) -> Option<u64> {
+        self.mul(a, &(k % self.0))
+    }
+}
+
+/// Arithmetic on `BigUint`s, which never overflows.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Big;
+
+impl Arith for Big {
+    type T = BigUint;
+
+    fn zero(&self) -> BigUint {
+        Zero::zero()
+    }
+
+    fn one(&self) -> BigUint {
+        One::one()
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> Option<BigUint> {
+        Some(a + b)
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> Option<BigUint> {
+        if b > a { None } else { Some(a - b) }
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> Option<BigUint> {
+        Some(a * b)
+    }
+
+    fn scale(&self, a: &BigUint, k: u64) -> Option<BigUint> {
+        Some(a * k)
+    }
+}
@@ -13,37 +13,204 @@
 extern crate num;
 use self::num::{BigInt, BigUint, One, Zero, pow};
 use self::num::bigint::ToBigUint;
+use arith::{Arith, Big, Modular};
+use combinatorics::{Sequence, Triangle};
+use continued_fractions;
+use decimal::{BigDecimal, Rounding};
+use primes;
+use std::iter;
 
 /// Returns n factorial as a `BigUint`.
 ///
+/// Multiplies the prime powers of n! from Legendre's formula in a balanced product tree.
+///
 /// ```
+/// extern crate num;
+/// extern crate euler_library;
 /// use euler_library::big as eu_big;
+/// use num::{BigUint, One};
 ///
+/// # fn main() {
 /// let big_number_string = eu_big::factorial(31).to_string();
 /// assert_eq!(big_number_string.to_string(), "8222838654177922817725562880000000");
+///
+/// for n in [0, 1, 2, 10, 97, 500] {
+///     let naive = (2..n + 1).fold(BigUint::one(), |acc, i| acc * BigUint::from(i as usize));
+///     assert_eq!(eu_big::factorial(n), naive);
+/// }
+/// # }
 /// ```
 pub fn factorial(n: usize) -> BigUint {
-    let mut fact: BigUint = One::one();
-    if n > 1 {
-        for i in 2..n + 1 {
-            fact = fact * i.to_biguint().unwrap()
-        }
+    prime_power_product(primes::sieve(n).into_iter().map(|p| (p, legendre(n, p))))
+}
+
+/// Returns the binomial coefficient n choose k as a `BigUint`, zero when k > n.
+///
+/// ```
+/// use euler_library::big as eu_big;
+///
+/// assert_eq!(eu_big::binomial(100, 50).to_string(), "100891344545564193334812497256");
+/// assert_eq!(eu_big::binomial(100, 50), eu_big::factorial(100) / (eu_big::factorial(50) * eu_big::factorial(50)));
+/// assert_eq!(eu_big::binomial(5, 6).to_string(), "0");
+/// ```
+pub fn binomial(n: usize, k: usize) -> BigUint {
+    if k > n {
+        return Zero::zero();
     }
-    fact
+    multinomial(&[k, n - k])
 }
 
-/// Returns `BigUnit` square root of usize n to digits precision.
+/// Returns the multinomial coefficient (k1 + k2 + ...)! / (k1! k2! ...) as a `BigUint`.
+///
+/// ```
+/// use euler_library::big as eu_big;
 ///
-/// Function does not tell you where decimal is.
-/// For example square root of 2 is 1.1414213..., function return 11414213...
+/// // arrangements of MISSISSIPPI
+/// assert_eq!(eu_big::multinomial(&[1, 4, 4, 2]).to_string(), "34650");
+/// let fs = eu_big::factorial(4) * eu_big::factorial(5) * eu_big::factorial(6);
+/// assert_eq!(eu_big::multinomial(&[4, 5, 6]), eu_big::factorial(15) / fs);
+/// ```
+pub fn multinomial(ks: &[usize]) -> BigUint {
+    let n = ks.iter().sum();
+    prime_power_product(primes::sieve(n)
+        .into_iter()
+        .map(|p| (p, legendre(n, p) - ks.iter().map(|&k| legendre(k, p)).sum::<usize>())))
+}
+
+/// Returns the double factorial n!! = n (n - 2) (n - 4) ... as a `BigUint`.
 ///
 /// ```
+/// extern crate num;
+/// extern crate euler_library;
 /// use euler_library::big as eu_big;
+/// use num::{BigUint, One};
+///
+/// # fn main() {
+/// assert_eq!(eu_big::double_factorial(9).to_string(), "945");
+/// assert_eq!(eu_big::double_factorial(10).to_string(), "3840");
+///
+/// for n in [0, 1, 2, 3, 51, 300] {
+///     let naive = (1..n + 1).rev().step_by(2).fold(BigUint::one(), |acc, i| acc * BigUint::from(i as usize));
+///     assert_eq!(eu_big::double_factorial(n), naive);
+/// }
+/// # }
+/// ```
+pub fn double_factorial(n: usize) -> BigUint {
+    let m = n / 2;
+    if n.is_multiple_of(2) {
+        // (2m)!! = 2^m m!
+        return factorial(m) << m;
+    }
+    // (2m + 1)!! = (2m + 1)! / (2^m m!)
+    prime_power_product(primes::sieve(n).into_iter().skip(1).map(|p| (p, legendre(n, p) - legendre(m, p))))
+}
+
+/// Returns T(n, k) of a `combinatorics::Triangle` as a `BigUint`.
 ///
-/// let sqrt_2_string = eu_big::precision_sqrt(2, 40).to_string();
-/// assert_eq!(sqrt_2_string.to_string(), "1414213562373095048801688724209698078569");
 /// ```
-pub fn precision_sqrt(n: usize, digits: usize) -> BigUint {
+/// use euler_library::big as eu_big;
+/// use euler_library::combinatorics::Triangle;
+///
+/// assert_eq!(eu_big::triangle(Triangle::Stirling2, 100, 3).to_string(),
+///            "85896253455335221205584888180155511368666317646");
+/// assert_eq!(eu_big::triangle(Triangle::Binomial, 100, 50), eu_big::binomial(100, 50));
+/// ```
+pub fn triangle(t: Triangle, n: usize, k: usize) -> BigUint {
+    t.value(&Big, n, k).unwrap()
+}
+
+/// Returns rows 0 to n of a `combinatorics::Triangle` as `BigUint`s.
+///
+/// ```
+/// use euler_library::big as eu_big;
+/// use euler_library::combinatorics::Triangle;
+///
+/// let rows = eu_big::triangle_table(Triangle::Stirling1, 30);
+/// assert_eq!(rows[30][1], eu_big::factorial(29));
+/// ```
+pub fn triangle_table(t: Triangle, n: usize) -> Vec<Vec<BigUint>> {
+    t.rows(&Big, n, n, true).unwrap()
+}
+
+/// Returns a(n) of a `combinatorics::Sequence` as a `BigUint`.
+///
+/// ```
+/// use euler_library::big as eu_big;
+/// use euler_library::combinatorics::Sequence;
+///
+/// assert_eq!(eu_big::sequence(Sequence::Catalan, 100).to_string(),
+///            "896519947090131496687170070074100632420837521538745909320");
+/// assert_eq!(eu_big::sequence(Sequence::Bell, 30).to_string(), "846749014511809332450147");
+/// ```
+pub fn sequence(s: Sequence, n: usize) -> BigUint {
+    s.terms(&Big, n).unwrap().swap_remove(n)
+}
+
+/// Returns a(0) to a(n) of a `combinatorics::Sequence` as `BigUint`s.
+///
+/// ```
+/// use euler_library::big as eu_big;
+/// use euler_library::combinatorics::Sequence;
+///
+/// // the subfactorial is the nearest integer to n! / e
+/// let ds = eu_big::sequence_table(Sequence::Subfactorial, 25);
+/// assert_eq!(ds[25].to_string(), "5706255282633466762357224");
+/// ```
+pub fn sequence_table(s: Sequence, n: usize) -> Vec<BigUint> {
+    s.terms(&Big, n).unwrap()
+}
+
+// exponent of prime p in n!
+fn legendre(mut n: usize, p: usize) -> usize {
+    let mut e = 0;
+    while n > 0 {
+        n /= p;
+        e += n
+    }
+    e
+}
+
+fn prime_power_product<I>(pes: I) -> BigUint
+    where I: Iterator<Item = (usize, usize)>
+{
+    let powers = pes.filter(|&(_, e)| e > 0)
+        .map(|(p, e)| pow(p.to_biguint().unwrap(), e))
+        .collect::<Vec<_>>();
+    product(&powers)
+}
+
+// product tree, keeps the operands of each multiplication about the same size
+fn product(xs: &[BigUint]) -> BigUint {
+    match xs.len() {
+        0 => One::one(),
+        1 => xs[0].clone(),
+        n => product(&xs[..n / 2]) * product(&xs[n / 2..]),
+    }
+}
+
+/// Returns the square root of usize n to digits significant digits.
+///
+/// Digits are truncated, not rounded. The integer part is always returned in full.
+///
+/// ```
+/// use euler_library::big as eu_big;
+///
+/// let sqrt_2 = eu_big::precision_sqrt(2, 40);
+/// assert_eq!(sqrt_2.to_string(), "1.414213562373095048801688724209698078569");
+/// assert_eq!(eu_big::precision_sqrt(200, 10).to_string(), "14.14213562");
+/// assert_eq!(eu_big::precision_sqrt(99, 5).to_string(), "9.9498");
+/// assert_eq!(eu_big::precision_sqrt(12345, 2).to_string(), "111");
+///
+/// // Problem 80: digital sum of the first hundred decimal digits
+/// let digits = eu_big::precision_sqrt(2, 100);
+/// assert_eq!(digits.mantissa().to_string().bytes().map(|b| (b - b'0') as usize).sum::<usize>(), 475);
+/// ```
+pub fn precision_sqrt(n: usize, digits: usize) -> BigDecimal {
+    if n == 0 {
+        return BigDecimal::new(Zero::zero(), digits.saturating_sub(1));
+    }
+    let int_digits = n.to_string().len().div_ceil(2);
+    let digits = digits.max(int_digits);
     let ten = &10.to_biguint().unwrap();
     let hundred = &(ten * ten);
     let limit = pow(ten.clone(), digits + 1);
@@ -58,14 +225,110 @@ pub fn precision_sqrt(n: usize, digits: usize) -> BigUint {
             b = (b.clone() / ten) * hundred + 5.to_biguint().unwrap();
         }
     }
-    b / hundred
+    BigDecimal::new(BigInt::from(b / hundred), digits - int_digits)
+}
+
+/// Returns the integer square root of n, the largest x with x * x <= n.
+///
+/// Newton's iteration.
+///
+/// ```
+/// extern crate num;
+/// extern crate euler_library;
+/// use euler_library::big as eu_big;
+/// use num::BigUint;
+///
+/// # fn main() {
+/// let n = "152415787532388367504942236884722755800955129".parse::<BigUint>().unwrap();
+/// assert_eq!(eu_big::isqrt(&n).to_string(), "12345678901234567890123");
+/// assert_eq!(eu_big::isqrt(&BigUint::from(99usize)).to_string(), "9");
+/// # }
+/// ```
+pub fn isqrt(n: &BigUint) -> BigUint {
+    iroot(n, 2)
+}
+
+/// Returns the integer k-th root of n, the largest x with x^k <= n.
+///
+/// Newton's iteration from a power of two above the root. Panics if k is zero.
+///
+/// ```
+/// extern crate num;
+/// extern crate euler_library;
+/// use euler_library::big as eu_big;
+/// use num::{BigUint, pow};
+///
+/// # fn main() {
+/// let n = pow(BigUint::from(10usize), 100);
+/// assert_eq!(eu_big::iroot(&n, 3).to_string(), "2154434690031883721759293566519350");
+/// assert_eq!(eu_big::iroot(&BigUint::from(1024usize), 10).to_string(), "2");
+/// assert_eq!(eu_big::iroot(&BigUint::from(1023usize), 10).to_string(), "1");
+/// # }
+/// ```
+pub fn iroot(n: &BigUint, k: usize) -> BigUint {
+    assert!(k > 0, "zeroth root");
+    if k == 1 || n.is_zero() {
+        return n.clone();
+    }
+    let k_big = k.to_biguint().unwrap();
+    let bits = n.bits() as usize;
+    let mut x: BigUint = BigUint::one() << bits.div_ceil(k);
+    loop {
+        let y = (&x * (k - 1).to_biguint().unwrap() + n / pow(x.clone(), k - 1)) / &k_big;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Returns the k-th root of the fraction numerator / denominator to scale decimal places.
+/// Panics if the denominator or k is zero.
+///
+/// ```
+/// extern crate num;
+/// extern crate euler_library;
+/// use euler_library::big as eu_big;
+/// use euler_library::decimal::Rounding;
+/// use num::BigUint;
+///
+/// # fn main() {
+/// let (two, three) = (BigUint::from(2usize), BigUint::from(3usize));
+/// let one = BigUint::from(1usize);
+///
+/// let cbrt_2 = eu_big::precision_root(&two, &one, 3, 30, Rounding::Truncate);
+/// assert_eq!(cbrt_2.to_string(), "1.259921049894873164767210607278");
+///
+/// // sqrt(2/3) = 0.81649658092772603273...
+/// assert_eq!(eu_big::precision_root(&two, &three, 2, 5, Rounding::Truncate).to_string(), "0.81649");
+/// assert_eq!(eu_big::precision_root(&two, &three, 2, 5, Rounding::Nearest).to_string(), "0.81650");
+/// # }
+/// ```
+pub fn precision_root(numerator: &BigUint,
+                      denominator: &BigUint,
+                      k: usize,
+                      scale: usize,
+                      rounding: Rounding)
+                      -> BigDecimal {
+    let shifted = numerator * pow(10.to_biguint().unwrap(), k * scale);
+    let mut root = iroot(&(&shifted / denominator), k);
+    if rounding == Rounding::Nearest {
+        // round up when root + 1/2 <= the exact root
+        let two: BigUint = 2.to_biguint().unwrap();
+        let half_up = &root * &two + BigUint::one();
+        if pow(half_up, k) * denominator <= pow(two, k) * shifted {
+            root += BigUint::one();
+        }
+    }
+    BigDecimal::new(BigInt::from(root), scale)
 }
 
 /// Return (numerator, denominator) after evaluating `continued_fraction`.
 ///
 /// Form of continued fraction: (a0, [t1, t2, t3, ....]).
-/// see `euler_library::common::continued_fraction` to generate a periodic continued fraction.
-/// `http://roycrippen.github.io/euler_library/euler_library/common/fn.continued_fraction.html`
+/// see `euler_library::common::sqrt_terms` to generate a periodic continued fraction,
+/// and `euler_library::continued_fractions::big_convergents` for lazy convergents.
+/// `http://roycrippen.github.io/euler_library/euler_library/continued_fractions/index.html`
 ///
 /// ```
 /// use euler_library::big as eu_big;
@@ -78,19 +341,54 @@ pub fn precision_sqrt(n: usize, digits: usize) -> BigUint {
 /// assert_eq!(numerator.to_string(), "3468077590434524694871282564");
 /// assert_eq!(denominator.to_string(), "723144166673926627543073281");
 /// ```
-pub fn continued_fraction(a0: usize, mut xs: Vec<usize>) -> (BigUint, BigUint) {
-    fn go(n: &BigUint, d: &BigUint, mut xs: Vec<BigUint>) -> (BigUint, BigUint) {
-        if xs.is_empty() {
-            return (n.clone(), d.clone());
+pub fn continued_fraction(a0: usize, xs: Vec<usize>) -> (BigUint, BigUint) {
+    continued_fractions::big_convergents(iter::once(a0).chain(xs)).last().unwrap()
+}
+
+// p(0..=n) by Euler's pentagonal number theorem, whose partial sums are never negative
+fn pentagonal_table<A: Arith>(ar: &A, n: usize) -> Vec<A::T> {
+    let mut p = vec![ar.one()];
+    for i in 1..n + 1 {
+        let mut total = ar.zero();
+        let mut j = 1;
+        while j * (3 * j - 1) / 2 <= i {
+            for k in [j * (3 * j - 1) / 2, j * (3 * j + 1) / 2] {
+                if k <= i {
+                    let next = if j % 2 == 1 { ar.add(&total, &p[i - k]) } else { ar.sub(&total, &p[i - k]) };
+                    total = next.expect("partition count overflows");
+                }
+            }
+            j += 1;
         }
-        let a = xs.pop().unwrap();
-        go(&(a * n + d), n, xs)
+        p.push(total);
     }
+    p
+}
+
+// partitions of 0..=n into parts from the given set, each part used any number of times
+// or at most once
+fn parts_table<A: Arith>(ar: &A, n: usize, parts: &[usize], distinct: bool) -> Vec<A::T> {
+    let mut parts = parts.iter().cloned().filter(|&k| k > 0 && k <= n).collect::<Vec<_>>();
+    parts.sort();
+    parts.dedup();
+    let mut table = vec![ar.zero(); n + 1];
+    table[0] = ar.one();
+    for k in parts {
+        let is = (k..n + 1).collect::<Vec<_>>();
+        let is: Vec<usize> = if distinct { is.into_iter().rev().collect() } else { is };
+        for i in is {
+            table[i] = ar.add(&table[i], &table[i - k]).expect("partition count overflows");
+        }
+    }
+    table
+}
 
-    xs.insert(0, a0);
-    let a = xs.pop().unwrap().to_biguint().unwrap();
-    let xs = xs.into_iter().map(|x| x.to_biguint().unwrap()).collect::<Vec<BigUint>>();
-    go(&a, &1.to_biguint().unwrap(), xs)
+fn to_bigints(xs: Vec<BigUint>) -> Vec<BigInt> {
+    xs.into_iter().map(BigInt::from).collect()
+}
+
+fn to_usizes(xs: Vec<u64>) -> Vec<usize> {
+    xs.into_iter().map(|x| x as usize).collect()
 }
 
 /// Returns a `BigInt` vec[p(0), p(1)...p(n)] generating partition function
@@ -103,24 +401,153 @@ pub fn continued_fraction(a0: usize, mut xs: Vec<usize>) -> (BigUint, BigUint) {
 /// let ps = eu_big::integer_partitions(1_000).iter().map(|x| x.to_string()).collect::<Vec<_>>();
 /// assert_eq!(&ps[23..26], ["1255", "1575", "1958"]);
 /// assert_eq!(&ps[1000..], ["24061467864032622473692149727991"]);
+///
+/// let small = eu_big::integer_partitions(2).iter().map(|x| x.to_string()).collect::<Vec<_>>();
+/// assert_eq!(small, ["1", "1", "2"]);
 /// ```
 pub fn integer_partitions(n: usize) -> Vec<BigInt> {
-    let k = (1..n)
-        .flat_map(|i| vec![i * (3 * i - 1) / 2, i * (3 * i - 1) / 2 + i])
-        .collect::<Vec<_>>();
+    to_bigints(pentagonal_table(&Big, n))
+}
 
-    let one: BigInt = One::one();
-    let mut p: Vec<BigInt> = vec![one.clone()];
-    let sign: Vec<BigInt> = vec![one.clone(), one.clone(), -one.clone(), -one.clone()];
+/// Returns vec[p(0), p(1)...p(n)] of the partition function mod m.
+///
+/// Panics if m is zero.
+///
+/// ```
+/// use euler_library::big as eu_big;
+///
+/// assert_eq!(eu_big::integer_partitions_mod(10, 7), [1, 1, 2, 3, 5, 0, 4, 1, 1, 2, 0]);
+///
+/// // Problem 78 with 10^3 in place of 10^6
+/// let ps = eu_big::integer_partitions_mod(1_000, 1_000);
+/// assert_eq!(ps.iter().skip(1).position(|&p| p == 0).map(|n| n + 1), Some(449));
+/// ```
+pub fn integer_partitions_mod(n: usize, m: usize) -> Vec<usize> {
+    to_usizes(pentagonal_table(&Modular(m as u64), n))
+}
 
-    for i in 1..n + 1 {
-        let mut temp: BigInt = Zero::zero();
-        let mut j = 0;
-        while k[j] <= i {
-            temp = temp + (p[i - k[j]].clone()) * sign[j % 4].clone();
-            j += 1;
-        }
-        p.push(temp)
-    }
-    p
+/// Returns a `BigInt` vec[q(0), q(1)...q(n)] of the partitions into distinct parts.
+///
+/// http://oeis.org/A000009/list
+///
+/// ```
+/// use euler_library::big as eu_big;
+///
+/// let qs = eu_big::distinct_partitions(12).iter().map(|x| x.to_string()).collect::<Vec<_>>();
+/// assert_eq!(qs, ["1", "1", "1", "2", "2", "3", "4", "5", "6", "8", "10", "12", "15"]);
+/// assert_eq!(eu_big::distinct_partitions(200)[200].to_string(), "487067746");
+/// ```
+pub fn distinct_partitions(n: usize) -> Vec<BigInt> {
+    let parts = (1..n + 1).collect::<Vec<_>>();
+    to_bigints(parts_table(&Big, n, &parts, true))
+}
+
+/// Returns vec[q(0), q(1)...q(n)] of the partitions into distinct parts mod m.
+///
+/// Panics if m is zero.
+///
+/// ```
+/// use euler_library::big as eu_big;
+///
+/// assert_eq!(eu_big::distinct_partitions_mod(12, 5), [1, 1, 1, 2, 2, 3, 4, 0, 1, 3, 0, 2, 0]);
+/// ```
+pub fn distinct_partitions_mod(n: usize, m: usize) -> Vec<usize> {
+    let parts = (1..n + 1).collect::<Vec<_>>();
+    to_usizes(parts_table(&Modular(m as u64), n, &parts, true))
+}
+
+/// Returns a `BigInt` vec of the number of partitions of 0, 1...n into parts from a set,
+/// each part used any number of times.
+///
+/// Parts may be given in any order, repeated or zero parts are ignored.
+///
+/// ```
+/// use euler_library::big as eu_big;
+///
+/// // Problem 31: ways to make 2 pounds from English coins
+/// let coins = [1, 2, 5, 10, 20, 50, 100, 200];
+/// assert_eq!(eu_big::partitions_with_parts(200, &coins)[200].to_string(), "73682");
+///
+/// let odd = eu_big::partitions_with_parts(7, &[1, 3, 5, 7]).iter().map(|x| x.to_string()).collect::<Vec<_>>();
+/// assert_eq!(odd, ["1", "1", "1", "2", "2", "3", "4", "5"]);
+/// ```
+pub fn partitions_with_parts(n: usize, parts: &[usize]) -> Vec<BigInt> {
+    to_bigints(parts_table(&Big, n, parts, false))
+}
+
+/// Returns a vec of the number of partitions of 0, 1...n into parts from a set mod m,
+/// see `partitions_with_parts`.
+///
+/// Panics if m is zero.
+///
+/// ```
+/// use euler_library::big as eu_big;
+///
+/// let coins = [1, 2, 5, 10, 20, 50, 100, 200];
+/// assert_eq!(eu_big::partitions_with_parts_mod(200, &coins, 1_000)[200], 682);
+/// ```
+pub fn partitions_with_parts_mod(n: usize, parts: &[usize], m: usize) -> Vec<usize> {
+    to_usizes(parts_table(&Modular(m as u64), n, parts, false))
+}
+
+/// Returns a `BigInt` vec of the number of partitions of 0, 1...n into at most k parts.
+///
+/// By conjugation, the same as partitions into parts no larger than k.
+///
+/// ```
+/// use euler_library::big as eu_big;
+///
+/// let two_parts = eu_big::partitions_at_most(6, 2).iter().map(|x| x.to_string()).collect::<Vec<_>>();
+/// assert_eq!(two_parts, ["1", "1", "2", "2", "3", "3", "4"]);
+/// assert_eq!(eu_big::partitions_at_most(100, 100), eu_big::integer_partitions(100));
+/// ```
+pub fn partitions_at_most(n: usize, k: usize) -> Vec<BigInt> {
+    partitions_with_parts(n, &(1..k + 1).collect::<Vec<_>>())
+}
+
+/// Returns a vec of the number of partitions of 0, 1...n into at most k parts mod m.
+///
+/// Panics if m is zero.
+///
+/// ```
+/// use euler_library::big as eu_big;
+///
+/// assert_eq!(eu_big::partitions_at_most_mod(6, 3, 5), [1, 1, 2, 3, 4, 0, 2]);
+/// ```
+pub fn partitions_at_most_mod(n: usize, k: usize, m: usize) -> Vec<usize> {
+    partitions_with_parts_mod(n, &(1..k + 1).collect::<Vec<_>>(), m)
+}
+
+/// Returns a `BigInt` vec of the number of partitions of 0, 1...n into primes.
+///
+/// http://oeis.org/A000607/list
+///
+/// ```
+/// extern crate num;
+/// extern crate euler_library;
+/// use euler_library::big as eu_big;
+/// use num::BigInt;
+///
+/// # fn main() {
+/// // Problem 77: first value written as a sum of primes in over five thousand ways
+/// let ps = eu_big::prime_partitions(100);
+/// assert_eq!(ps.iter().position(|p| *p > BigInt::from(5000)), Some(71));
+/// assert_eq!(ps[10].to_string(), "5");
+/// # }
+/// ```
+pub fn prime_partitions(n: usize) -> Vec<BigInt> {
+    partitions_with_parts(n, &primes::sieve(n))
+}
+
+/// Returns a vec of the number of partitions of 0, 1...n into primes mod m.
+///
+/// Panics if m is zero.
+///
+/// ```
+/// use euler_library::big as eu_big;
+///
+/// assert_eq!(eu_big::prime_partitions_mod(10, 3), [1, 0, 1, 1, 1, 2, 2, 0, 0, 1, 2]);
+/// ```
+pub fn prime_partitions_mod(n: usize, m: usize) -> Vec<usize> {
+    partitions_with_parts_mod(n, &primes::sieve(n), m)
 }
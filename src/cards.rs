@@ -15,22 +15,38 @@
 //! assert_eq!(hand_winner.show(), "[(Two, Diamonds), (Two, Spades), (Three, Clubs), (Three, Hearts), (Three, Diamonds)]");
 //!
 //! assert!(hand_winner.get_rank() > hand_loser.get_rank());
+//!
+//! // a whole file of head-to-head hands, one match per line
+//! let matches = "8C TS KC 9H 4S 7D 2S 5D 3S AC\n5C AD 5D AC 9C 7C 5H 8D TD KS\n";
+//! let matches = eu_cards::read_matches(matches.as_bytes()).collect::<Result<Vec<_>, _>>().unwrap();
+//! assert_eq!(eu_cards::tally(matches), eu_cards::Tally { wins: 1, losses: 1, ties: 0 });
 //! ```
 
 use self::Val::*;
 use self::Suit::*;
 
 use std::fmt;
+use std::io::{BufRead, Lines};
+
+#[rustfmt::skip]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Val { Two = 2, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace, Joker, }
+
+#[rustfmt::skip]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Suit { Spades, Hearts, Diamonds, Clubs, Joker, }
 
-#[cfg_attr(rustfmt, rustfmt_skip)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub enum Val { Two = 2, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace, }
+/// The thirteen card face values of a standard deck in ascending order.
+#[rustfmt::skip]
+pub const VALS: [Val; 13] = [Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace];
 
-#[cfg_attr(rustfmt, rustfmt_skip)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub enum Suit { Spades, Hearts, Diamonds, Clubs, }
+/// The four suits of a standard deck.
+pub const SUITS: [Suit; 4] = [Spades, Hearts, Diamonds, Clubs];
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// A joker, written as "XX".
+pub const JOKER: Card = Card { val: Val::Joker, suit: Suit::Joker };
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Card {
     pub val: Val,
     pub suit: Suit,
@@ -59,6 +75,7 @@ impl Card {
             Jack => Queen,
             Queen => King,
             King => Ace,
+            Val::Joker => Val::Joker,
         }
     }
 }
@@ -85,7 +102,7 @@ impl Hand {
         }
         str.pop();
         str.pop();
-        str = str + "]";
+        str += "]";
         str
     }
     fn is_flush(&self) -> bool {
@@ -196,10 +213,93 @@ impl Hand {
         }
         res
     }
+    /// Returns the rank of the hand, higher is better.
+    ///
+    /// Jokers are always wild, see `get_rank_wild`.
+    ///
+    /// ```
+    /// use euler_library::cards as eu_cards;
+    ///
+    /// let hand = eu_cards::get_hand(5, "XX 9H TH JH QH".chars().filter(|&x| x != ' ').collect());
+    /// assert_eq!(hand.get_rank(), eu_cards::get_hand(5, "KH 9H TH JH QH".chars().filter(|&x| x != ' ').collect()).get_rank());
+    /// ```
     pub fn get_rank(&self) -> usize {
+        if self.cards.iter().any(|c| c.val == Val::Joker) {
+            return self.get_rank_wild(&[]);
+        }
+        self.rank_natural()
+    }
+    /// Returns the rank of the hand with jokers and every card valued in wilds treated as wild.
+    ///
+    /// Each wild card stands in for whichever card makes the best hand. A wild may repeat the
+    /// value of a card held, so five of a kind counts, ranking above a straight flush, but never
+    /// the card itself: a flush needs a free card of its suit for every wild.
+    ///
+    /// ```
+    /// use euler_library::cards as eu_cards;
+    /// use euler_library::cards::Val::Two;
+    ///
+    /// // deuces wild
+    /// let five_aces = eu_cards::get_hand(5, "2D 2C AH AS AD".chars().filter(|&x| x != ' ').collect());
+    /// let royal = eu_cards::get_hand(5, "TS JS QS KS AS".chars().filter(|&x| x != ' ').collect());
+    /// assert!(five_aces.get_rank_wild(&[Two]) > royal.get_rank_wild(&[Two]));
+    ///
+    /// let quads = eu_cards::get_hand(5, "2D 7C 7H 9S 7D".chars().filter(|&x| x != ' ').collect());
+    /// assert_eq!(quads.get_rank_wild(&[Two]) / 1_000_000, 7);
+    /// assert_eq!(quads.get_rank() / 1_000_000, 3);
+    ///
+    /// // the wilds make a flush to the ace, king and queen, not one holding three aces
+    /// let flush = eu_cards::get_hand(5, "2D 2C AH KH 9H".chars().filter(|&x| x != ' ').collect());
+    /// let best = eu_cards::get_hand(5, "AH KH QH JH 9H".chars().filter(|&x| x != ' ').collect());
+    /// assert_eq!(flush.get_rank_wild(&[Two]), best.get_rank());
+    /// ```
+    pub fn get_rank_wild(&self, wilds: &[Val]) -> usize {
+        let (wild, natural): (Vec<Card>, Vec<Card>) =
+            self.cards.iter().partition(|c| c.val == Val::Joker || wilds.contains(&c.val));
+        if wild.is_empty() {
+            return self.rank_natural();
+        }
+        if natural.iter().all(|c| c.val == natural[0].val) {
+            let val = natural.first().map_or(Ace, |c| c.val);
+            return 9_000_000 + val as usize;
+        }
+
+        // a wild matching the suit of the naturals is never worse than one that does not
+        let flush_suit = Some(natural[0].suit).filter(|&s| natural.iter().all(|c| c.suit == s));
+
+        // try every multiset of values for the wilds, each taking the flush suit if that card
+        // is free, else any free suit, there being at most four cards of a value
+        let mut idx = vec![0; wild.len()];
+        let mut best = 0;
+        loop {
+            let mut cards = natural.clone();
+            for &i in &idx {
+                let val = VALS[i];
+                let suit = flush_suit
+                    .into_iter()
+                    .chain(SUITS.iter().cloned())
+                    .find(|&suit| !cards.contains(&Card { val, suit }))
+                    .unwrap();
+                cards.push(Card { val, suit });
+            }
+            best = best.max(Hand { cards }.rank_natural());
+
+            match idx.iter().rposition(|&i| i < VALS.len() - 1) {
+                Some(p) => {
+                    let next = idx[p] + 1;
+                    for i in idx.iter_mut().skip(p) {
+                        *i = next;
+                    }
+                }
+                None => break,
+            }
+        }
+        best
+    }
+    fn rank_natural(&self) -> usize {
         let mut cards = self.cards.clone();
         cards.sort();
-        let hand = Hand { cards: cards };
+        let hand = Hand { cards };
         let group = hand.group();
         let mut rank: usize = 0;
         if hand.is_straight_flush() {
@@ -299,12 +399,13 @@ pub fn show_grp(gss: Vec<Hand>) -> String {
 
 /// Returns Suit enum from a character.
 ///
-/// Panics if character is invalid.
+/// Panics if character is invalid. 'X' is the suit of a joker.
 ///
 /// ```
 /// use euler_library::cards as eu_cards;
 ///
 /// assert_eq!(eu_cards::char_to_suit('H'), eu_cards::Suit::Hearts);
+/// assert_eq!(eu_cards::char_to_suit('X'), eu_cards::Suit::Joker);
 ///
 /// ```
 pub fn char_to_suit(c: char) -> Suit {
@@ -313,19 +414,21 @@ pub fn char_to_suit(c: char) -> Suit {
         'H' => Hearts,
         'D' => Diamonds,
         'C' => Clubs,
-        _ => panic!(format!("error getting suit: {}", c)),
+        'X' => Suit::Joker,
+        _ => panic!("error getting suit: {}", c),
     }
 }
 
 /// Returns card face Val enum from a character.
 ///
-/// Panics if character is invalid.
+/// Panics if character is invalid. 'X' is a joker.
 ///
 /// ```
 /// use euler_library::cards as eu_cards;
 ///
 /// assert_eq!(eu_cards::char_to_val('A'), eu_cards::Val::Ace);
 /// assert_eq!(eu_cards::char_to_val('9'), eu_cards::Val::Nine);
+/// assert_eq!(eu_cards::char_to_val('X'), eu_cards::Val::Joker);
 ///
 /// ```
 pub fn char_to_val(c: char) -> Val {
@@ -343,7 +446,8 @@ pub fn char_to_val(c: char) -> Val {
         'Q' => Queen,
         'K' => King,
         'A' => Ace,
-        _ => panic!(format!("error getting value: {}", c)),
+        'X' => Val::Joker,
+        _ => panic!("error getting value: {}", c),
     }
 }
 
@@ -367,7 +471,120 @@ pub fn get_hand(cnt: usize, mut cs: Vec<char>) -> Hand {
     for _ in 0..cnt {
         let suit = char_to_suit(cs.pop().unwrap());
         let val = char_to_val(cs.pop().unwrap());
-        cards.push(Card { suit: suit, val: val })
+        cards.push(Card { suit, val })
+    }
+    Hand { cards }
+}
+
+/// Returns the card written as value then suit, such as "TD" or "Td".
+///
+/// Unlike `char_to_val` and `char_to_suit` an invalid card is an error, not a panic.
+///
+/// ```
+/// use euler_library::cards as eu_cards;
+///
+/// let card = eu_cards::Card { val: eu_cards::Val::Ten, suit: eu_cards::Suit::Diamonds };
+/// assert_eq!(eu_cards::parse_card("Td"), Ok(card));
+/// assert_eq!(eu_cards::parse_card("XX"), Ok(eu_cards::JOKER));
+/// assert!(eu_cards::parse_card("1d").is_err());
+/// assert!(eu_cards::parse_card("AX").is_err());
+/// assert!(eu_cards::parse_card("XS").is_err());
+/// ```
+pub fn parse_card(s: &str) -> Result<Card, String> {
+    let cs = s.chars().map(|c| c.to_ascii_uppercase()).collect::<Vec<_>>();
+    if cs == ['X', 'X'] {
+        return Ok(JOKER);
+    }
+    if cs.len() != 2 || !"23456789TJQKA".contains(cs[0]) || !"SHDC".contains(cs[1]) {
+        return Err(format!("invalid card: {}", s));
+    }
+    Ok(Card { val: char_to_val(cs[0]), suit: char_to_suit(cs[1]) })
+}
+
+/// Iterator of the hands of a match file, see `read_matches`.
+pub struct Matches<R> {
+    lines: Lines<R>,
+    line: usize,
+}
+
+impl<R: BufRead> Iterator for Matches<R> {
+    type Item = Result<(Hand, Hand), String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+            self.line += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(format!("line {}: {}", self.line, e))),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let cards = line.split_whitespace().map(parse_card).collect::<Result<Vec<_>, _>>();
+            return Some(match cards {
+                Ok(ref cards) if cards.len() == 10 => {
+                    Ok((Hand { cards: cards[..5].to_vec() }, Hand { cards: cards[5..].to_vec() }))
+                }
+                Ok(cards) => Err(format!("line {}: expected 10 cards, found {}", self.line, cards.len())),
+                Err(e) => Err(format!("line {}: {}", self.line, e)),
+            });
+        }
+    }
+}
+
+/// Returns an iterator of (player 1, player 2) hands from a match file.
+///
+/// Each line holds ten card codes, player 1's five then player 2's five, as in Problem 54.
+/// Blank lines are skipped and errors name their line.
+///
+/// ```
+/// use euler_library::cards as eu_cards;
+///
+/// let file = "5H 5C 6S 7S KD 2C 3S 8S 8D TD\n\n5D 8C 9S JS AC 2C 5C 7D 8S\n";
+/// let mut matches = eu_cards::read_matches(file.as_bytes());
+///
+/// let (player1, player2) = matches.next().unwrap().unwrap();
+/// assert!(player1.get_rank() < player2.get_rank());
+/// assert_eq!(matches.next(), Some(Err("line 3: expected 10 cards, found 9".to_string())));
+/// assert_eq!(matches.next(), None);
+/// ```
+pub fn read_matches<R: BufRead>(reader: R) -> Matches<R> {
+    Matches { lines: reader.lines(), line: 0 }
+}
+
+/// Player 1's results over a set of matches.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub wins: usize,
+    pub losses: usize,
+    pub ties: usize,
+}
+
+/// Returns player 1's wins, losses and ties by `get_rank` over (player 1, player 2) hands.
+///
+/// ```
+/// use euler_library::cards as eu_cards;
+///
+/// let hand = |s: &str| eu_cards::get_hand(5, s.chars().filter(|&x| x != ' ').collect());
+/// let matches = vec![(hand("2H 2D 4C 4D 4S"), hand("3C 3D 3S 9S 9D")),
+///                    (hand("2D 9C AS AH AC"), hand("3D 6D 7D TD QD")),
+///                    (hand("4D 6S 9H QH QC"), hand("3D 6D 7H QD QS"))];
+/// assert_eq!(eu_cards::tally(matches), eu_cards::Tally { wins: 2, losses: 1, ties: 0 });
+/// ```
+pub fn tally<I>(matches: I) -> Tally
+    where I: IntoIterator<Item = (Hand, Hand)>
+{
+    let mut res = Tally::default();
+    for (player1, player2) in matches {
+        let (r1, r2) = (player1.get_rank(), player2.get_rank());
+        if r1 > r2 {
+            res.wins += 1
+        } else if r1 < r2 {
+            res.losses += 1
+        } else {
+            res.ties += 1
+        }
     }
-    Hand { cards: cards }
+    res
 }
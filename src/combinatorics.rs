@@ -0,0 +1,1211 @@
+//! Lazy combinatorial iterators and counting numbers.
+//!
+//! Each iterator yields `Vec`s of cloned items in lexicographic order of their positions
+//! in the input, so sorted input gives sorted output. Only the current positions are kept.
+//!
+//! The number families come as checked `u64` values, mod m values and tables of both.
+//! `big` has the `BigUint` versions.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::combinatorics as eu_comb;
+//!
+//! let xs = [1, 2, 3, 4];
+//! assert_eq!(eu_comb::permutations(&xs).count(), 24);
+//! assert_eq!(eu_comb::k_permutations(&xs, 2).nth(3), Some(vec![2, 1]));
+//! assert_eq!(eu_comb::combinations(&xs, 2).collect::<Vec<_>>(),
+//!            [[1, 2], [1, 3], [1, 4], [2, 3], [2, 4], [3, 4]]);
+//!
+//! // sums of three dice without building every roll
+//! let dice = [1, 2, 3, 4, 5, 6];
+//! let tens = eu_comb::cartesian_power(&dice, 3).filter(|roll| roll.iter().sum::<usize>() == 10).count();
+//! assert_eq!(tens, 27);
+//!
+//! assert_eq!(eu_comb::binomial(52, 5), Some(2_598_960));
+//! assert_eq!(eu_comb::sequence(eu_comb::Sequence::Bell, 5), Some(52));
+//! ```
+
+use arith::{Arith, Checked, Modular};
+use primes;
+use std::convert::TryFrom;
+
+/// Iterator of the k-permutations of a slice.
+pub struct KPermutations<'a, T: 'a> {
+    xs: &'a [T],
+    indices: Vec<usize>,
+    k: usize,
+    started: bool,
+    done: bool,
+}
+
+impl<'a, T> KPermutations<'a, T> {
+    // smallest position above from not used in the first len indices
+    fn next_unused(&self, from: usize, len: usize) -> Option<usize> {
+        (from..self.xs.len()).find(|j| !self.indices[..len].contains(j))
+    }
+
+    fn advance(&mut self) -> bool {
+        let n = self.xs.len();
+        if self.k == n {
+            // the usual next permutation, all positions are in use
+            let i = match (1..n).rev().find(|&i| self.indices[i - 1] < self.indices[i]) {
+                Some(i) => i - 1,
+                None => return false,
+            };
+            let j = (i + 1..n).rev().find(|&j| self.indices[j] > self.indices[i]).unwrap();
+            self.indices.swap(i, j);
+            self.indices[i + 1..].reverse();
+            return true;
+        }
+        for i in (0..self.k).rev() {
+            if let Some(j) = self.next_unused(self.indices[i] + 1, i) {
+                self.indices[i] = j;
+                for p in i + 1..self.k {
+                    self.indices[p] = self.next_unused(0, p).unwrap();
+                }
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<'a, T: Clone> Iterator for KPermutations<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            self.done = self.k > self.xs.len();
+        } else {
+            self.done = !self.advance();
+        }
+        if self.done {
+            return None;
+        }
+        Some(self.indices.iter().map(|&i| self.xs[i].clone()).collect())
+    }
+}
+
+/// Returns an iterator of the permutations of k items chosen from xs, without repetition.
+///
+/// Empty if k is larger than the number of items.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// let ps = eu_comb::k_permutations(&['a', 'b', 'c'], 2).map(|p| p.into_iter().collect::<String>());
+/// assert_eq!(ps.collect::<Vec<_>>(), ["ab", "ac", "ba", "bc", "ca", "cb"]);
+/// assert_eq!(eu_comb::k_permutations(&[1, 2], 0).collect::<Vec<_>>(), [Vec::<i32>::new()]);
+/// assert_eq!(eu_comb::k_permutations(&[1, 2], 3).count(), 0);
+/// ```
+pub fn k_permutations<T: Clone>(xs: &[T], k: usize) -> KPermutations<'_, T> {
+    KPermutations { xs, indices: (0..k).collect(), k, started: false, done: false }
+}
+
+/// Returns an iterator of the permutations of all items of xs.
+///
+/// Items are told apart by position, so repeated items give repeated permutations.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// let ps = eu_comb::permutations(&[1, 2, 3]).collect::<Vec<_>>();
+/// assert_eq!(ps, [[1, 2, 3], [1, 3, 2], [2, 1, 3], [2, 3, 1], [3, 1, 2], [3, 2, 1]]);
+///
+/// // Problem 24 for 0, 1 and 2
+/// let p = eu_comb::permutations(&[0, 1, 2]).nth(3).unwrap();
+/// assert_eq!(p, [1, 2, 0]);
+/// ```
+pub fn permutations<T: Clone>(xs: &[T]) -> KPermutations<'_, T> {
+    k_permutations(xs, xs.len())
+}
+
+/// Iterator of the k-combinations of a slice, with or without repetition.
+pub struct Combinations<'a, T: 'a> {
+    xs: &'a [T],
+    indices: Vec<usize>,
+    repeats: bool,
+    started: bool,
+    done: bool,
+}
+
+impl<'a, T: Clone> Iterator for Combinations<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let (n, k) = (self.xs.len(), self.indices.len());
+        if !self.started {
+            self.started = true;
+            let too_many = if self.repeats { n == 0 && k > 0 } else { k > n };
+            if too_many {
+                self.done = true;
+                return None;
+            }
+        } else {
+            // the rightmost position below its largest value
+            let max = |i: usize| if self.repeats { n - 1 } else { n - k + i };
+            let i = match (0..k).rev().find(|&i| self.indices[i] < max(i)) {
+                Some(i) => i,
+                None => {
+                    self.done = true;
+                    return None;
+                }
+            };
+            self.indices[i] += 1;
+            for j in i + 1..k {
+                self.indices[j] = if self.repeats { self.indices[i] } else { self.indices[j - 1] + 1 };
+            }
+        }
+        Some(self.indices.iter().map(|&i| self.xs[i].clone()).collect())
+    }
+}
+
+/// Returns an iterator of the combinations of k items chosen from xs, without repetition.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// let cs = eu_comb::combinations(&["a", "b", "c", "d"], 3).map(|c| c.concat()).collect::<Vec<_>>();
+/// assert_eq!(cs, ["abc", "abd", "acd", "bcd"]);
+/// assert_eq!(eu_comb::combinations(&(1..21).collect::<Vec<_>>(), 10).count(), 184_756);
+/// assert_eq!(eu_comb::combinations(&[1, 2], 3).count(), 0);
+/// ```
+pub fn combinations<T: Clone>(xs: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations { xs, indices: (0..k).collect(), repeats: false, started: false, done: false }
+}
+
+/// Returns an iterator of the combinations of k items chosen from xs, with repetition.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// let cs = eu_comb::combinations_with_replacement(&[1, 2, 3], 2).collect::<Vec<_>>();
+/// assert_eq!(cs, [[1, 1], [1, 2], [1, 3], [2, 2], [2, 3], [3, 3]]);
+/// assert_eq!(eu_comb::combinations_with_replacement(&[1, 2, 3, 4, 5, 6], 4).count(), 126);
+/// ```
+pub fn combinations_with_replacement<T: Clone>(xs: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations { xs, indices: vec![0; k], repeats: true, started: false, done: false }
+}
+
+/// Iterator of the cartesian product of slices.
+pub struct Product<'a, T: 'a> {
+    lists: Vec<&'a [T]>,
+    indices: Vec<usize>,
+    started: bool,
+    done: bool,
+}
+
+impl<'a, T: Clone> Iterator for Product<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            if self.lists.iter().any(|xs| xs.is_empty()) {
+                self.done = true;
+                return None;
+            }
+        } else {
+            // odometer, the last list turns fastest
+            let mut i = self.lists.len();
+            loop {
+                if i == 0 {
+                    self.done = true;
+                    return None;
+                }
+                i -= 1;
+                self.indices[i] += 1;
+                if self.indices[i] < self.lists[i].len() {
+                    break;
+                }
+                self.indices[i] = 0;
+            }
+        }
+        Some(self.indices.iter().zip(&self.lists).map(|(&i, xs)| xs[i].clone()).collect())
+    }
+}
+
+/// Returns an iterator of the cartesian product of lists, one item from each.
+///
+/// No lists give a single empty product.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// let xss = [vec![1, 2], vec![3, 4], vec![5]];
+/// let ps = eu_comb::cartesian_product(&xss).collect::<Vec<_>>();
+/// assert_eq!(ps, [[1, 3, 5], [1, 4, 5], [2, 3, 5], [2, 4, 5]]);
+/// ```
+pub fn cartesian_product<T: Clone>(lists: &[Vec<T>]) -> Product<'_, T> {
+    Product { lists: lists.iter().map(|xs| &xs[..]).collect(), indices: vec![0; lists.len()], started: false, done: false }
+}
+
+/// Returns an iterator of the k-tuples of items from xs, the cartesian product of k copies.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// let ps = eu_comb::cartesian_power(&[0, 1], 3).collect::<Vec<_>>();
+/// assert_eq!(ps[..4], [[0, 0, 0], [0, 0, 1], [0, 1, 0], [0, 1, 1]]);
+/// assert_eq!(eu_comb::cartesian_power(&["red", "green", "blue", "orange"], 8).count(), 65_536);
+/// ```
+pub fn cartesian_power<T: Clone>(xs: &[T], k: usize) -> Product<'_, T> {
+    Product { lists: vec![xs; k], indices: vec![0; k], started: false, done: false }
+}
+
+// n choose k, None if over usize
+fn choose(n: usize, k: usize) -> Option<usize> {
+    binomial(n, k).and_then(|c| usize::try_from(c).ok())
+}
+
+/// Returns the index of a k-subset of 0..n, given in increasing order, in the order of `combinations`.
+///
+/// Uses the combinatorial number system on the complements n - 1 - c.
+/// Panics if the subset is not increasing, has items not below n, or the count overflows usize.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// assert_eq!(eu_comb::combination_rank(5, &[0, 1, 2]), 0);
+/// assert_eq!(eu_comb::combination_rank(5, &[0, 2, 4]), 4);
+/// assert_eq!(eu_comb::combination_rank(5, &[2, 3, 4]), 9);
+///
+/// let xs = (0..8).collect::<Vec<_>>();
+/// let position = eu_comb::combinations(&xs, 4).position(|c| c == [1, 3, 4, 7]);
+/// assert_eq!(position, Some(eu_comb::combination_rank(8, &[1, 3, 4, 7])));
+/// ```
+pub fn combination_rank(n: usize, c: &[usize]) -> usize {
+    assert!(c.windows(2).all(|w| w[0] < w[1]), "subset not increasing");
+    assert!(c.last().is_none_or(|&x| x < n), "subset item out of range");
+    let k = c.len();
+    let total = choose(n, k).expect("combination count overflows usize");
+    // colexicographic rank of the complements, which come in reverse order
+    let colex = c.iter()
+        .rev()
+        .enumerate()
+        .map(|(j, &x)| choose(n - 1 - x, j + 1).unwrap())
+        .sum::<usize>();
+    total - 1 - colex
+}
+
+/// Returns the k-subset of 0..n at index i in the order of `combinations`, in increasing order.
+///
+/// Returns None if i is not below n choose k. Panics if n choose k overflows usize.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// assert_eq!(eu_comb::nth_combination(5, 3, 4), Some(vec![0, 2, 4]));
+/// assert_eq!(eu_comb::nth_combination(5, 3, 10), None);
+/// assert_eq!(eu_comb::nth_combination(60, 30, 10u64.pow(17) as usize).map(|c| eu_comb::combination_rank(60, &c)),
+///            Some(10u64.pow(17) as usize));
+/// ```
+pub fn nth_combination(n: usize, k: usize, i: usize) -> Option<Vec<usize>> {
+    let total = choose(n, k).expect("combination count overflows usize");
+    if i >= total {
+        return None;
+    }
+    let mut colex = total - 1 - i;
+    let mut c = Vec::with_capacity(k);
+    let mut d = n;
+    for j in (1..k + 1).rev() {
+        // the largest d with d choose j <= colex
+        d -= 1;
+        while choose(d, j).unwrap() > colex {
+            d -= 1;
+        }
+        colex -= choose(d, j).unwrap();
+        c.push(n - 1 - d);
+    }
+    Some(c)
+}
+
+/// Iterator of the k-subsets of 0..n in revolving door order.
+///
+/// Each subset differs from the one before by one item out and one item in,
+/// given by `last_swap`.
+pub struct RevolvingDoor {
+    // c[1..k + 1] is the subset in increasing order and c[k + 1] = n, as in Knuth's Algorithm R
+    c: Vec<usize>,
+    k: usize,
+    swap: Option<(usize, usize)>,
+    started: bool,
+    done: bool,
+}
+
+impl RevolvingDoor {
+    /// Returns the (removed, added) items of the last step, None before the second subset.
+    ///
+    /// ```
+    /// use euler_library::combinatorics as eu_comb;
+    ///
+    /// // subset sums updated in constant time per step
+    /// let xs = [3, 5, 8, 13, 21];
+    /// let mut door = eu_comb::revolving_door(5, 2);
+    /// let first = door.next().unwrap();
+    /// let mut sum = first.iter().map(|&i| xs[i]).sum::<usize>();
+    /// let mut sums = vec![sum];
+    /// while door.next().is_some() {
+    ///     let (out, inn) = door.last_swap().unwrap();
+    ///     sum = sum + xs[inn] - xs[out];
+    ///     sums.push(sum);
+    /// }
+    /// sums.sort();
+    /// assert_eq!(sums, [8, 11, 13, 16, 18, 21, 24, 26, 29, 34]);
+    /// ```
+    pub fn last_swap(&self) -> Option<(usize, usize)> {
+        self.swap
+    }
+
+    fn advance(&mut self) -> bool {
+        let (c, k) = (&mut self.c, self.k);
+        if k == 0 {
+            return false;
+        }
+        if k % 2 == 1 {
+            if c[1] + 1 < c[2] {
+                self.swap = Some((c[1], c[1] + 1));
+                c[1] += 1;
+                return true;
+            }
+        } else if c[1] > 0 {
+            self.swap = Some((c[1], c[1] - 1));
+            c[1] -= 1;
+            return true;
+        }
+        let mut j = 2;
+        // odd k starts by trying to decrease c[2], even k by trying to increase it
+        let mut decrease = k % 2 == 1;
+        while j <= k {
+            if decrease && c[j] >= j {
+                self.swap = Some((c[j], j - 2));
+                c[j] = c[j - 1];
+                c[j - 1] = j - 2;
+                return true;
+            }
+            if !decrease && c[j] + 1 < c[j + 1] {
+                self.swap = Some((j - 2, c[j] + 1));
+                c[j - 1] = c[j];
+                c[j] += 1;
+                return true;
+            }
+            j += 1;
+            decrease = !decrease;
+        }
+        false
+    }
+}
+
+impl Iterator for RevolvingDoor {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.done {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+        } else if !self.advance() {
+            self.done = true;
+            return None;
+        }
+        Some(self.c[1..self.k + 1].to_vec())
+    }
+}
+
+/// Returns an iterator of the k-subsets of 0..n in revolving door order, starting at 0..k.
+///
+/// Consecutive subsets swap one item for another, so sums and products over the subset can be
+/// updated in constant time. Empty if k > n.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// let cs = eu_comb::revolving_door(4, 2).collect::<Vec<_>>();
+/// assert_eq!(cs, [[0, 1], [1, 2], [0, 2], [2, 3], [1, 3], [0, 3]]);
+/// assert_eq!(eu_comb::revolving_door(10, 4).count(), 210);
+/// ```
+pub fn revolving_door(n: usize, k: usize) -> RevolvingDoor {
+    let mut c = vec![0];
+    c.extend(0..k);
+    c.push(n);
+    RevolvingDoor { c, k, swap: None, started: false, done: k > n }
+}
+
+/// Iterator of the subsets of 0..n as bit masks in binary reflected Gray code order.
+pub struct GraySubsets {
+    n: usize,
+    i: usize,
+}
+
+impl Iterator for GraySubsets {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.n < usize::BITS as usize && self.i >> self.n != 0 {
+            return None;
+        }
+        let mask = self.i ^ (self.i >> 1);
+        self.i += 1;
+        Some(mask)
+    }
+}
+
+/// Returns an iterator of the 2^n subsets of 0..n as bit masks, starting from the empty set,
+/// each differing from the one before in one item.
+///
+/// The item changed is `(previous ^ mask).trailing_zeros()`. Panics if n is not below the bits
+/// of a usize.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// let masks = eu_comb::gray_subsets(3).collect::<Vec<_>>();
+/// assert_eq!(masks, [0b000, 0b001, 0b011, 0b010, 0b110, 0b111, 0b101, 0b100]);
+///
+/// // all subset sums, one addition or subtraction per subset
+/// let xs = [1, 2, 4, 8];
+/// let (mut prev, mut sum, mut sums) = (0, 0, vec![]);
+/// for mask in eu_comb::gray_subsets(4) {
+///     let changed = prev ^ mask;
+///     if changed != 0 {
+///         let x = xs[changed.trailing_zeros() as usize];
+///         sum = if mask & changed != 0 { sum + x } else { sum - x };
+///     }
+///     sums.push(sum);
+///     prev = mask;
+/// }
+/// sums.sort();
+/// assert_eq!(sums, (0..16).collect::<Vec<_>>());
+/// ```
+pub fn gray_subsets(n: usize) -> GraySubsets {
+    assert!(n < usize::BITS as usize, "too many items for a bit mask");
+    GraySubsets { n, i: 0 }
+}
+
+/// A triangle of numbers T(n, k) for 0 <= k <= n, by a recurrence
+/// T(n, k) = a(n, k) T(n - 1, k - 1) + b(n, k) T(n - 1, k) from T(0, 0) = 1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Triangle {
+    /// Binomial coefficients n choose k.
+    Binomial,
+    /// Unsigned Stirling numbers of the first kind, permutations of n with k cycles.
+    Stirling1,
+    /// Stirling numbers of the second kind, partitions of an n-set into k blocks.
+    Stirling2,
+    /// Eulerian numbers, permutations of n with k ascents.
+    Eulerian,
+}
+
+impl Triangle {
+    // (a, b) of the recurrence
+    fn coefficients(self, n: usize, k: usize) -> (u64, u64) {
+        let (n, k) = (n as u64, k as u64);
+        match self {
+            Triangle::Binomial => (1, 1),
+            Triangle::Stirling1 => (1, n - 1),
+            Triangle::Stirling2 => (1, k),
+            Triangle::Eulerian => (n - k, k + 1),
+        }
+    }
+
+    // rows 0..=n, each cut to columns 0..=cols, keeping only the last row unless all is set
+    pub(crate) fn rows<A: Arith>(self, ar: &A, n: usize, cols: usize, all: bool) -> Option<Vec<Vec<A::T>>> {
+        let mut rows = vec![vec![ar.one()]];
+        for i in 1..n + 1 {
+            let prev = rows.last().unwrap();
+            let mut row = Vec::with_capacity(i.min(cols) + 1);
+            for k in 0..i.min(cols) + 1 {
+                let (a, b) = self.coefficients(i, k);
+                let left = if k > 0 { ar.scale(&prev[k - 1], a)? } else { ar.zero() };
+                let above = if k < i { ar.scale(&prev[k], b)? } else { ar.zero() };
+                row.push(ar.add(&left, &above)?);
+            }
+            if !all {
+                rows.clear();
+            }
+            rows.push(row);
+        }
+        Some(rows)
+    }
+
+    pub(crate) fn value<A: Arith>(self, ar: &A, n: usize, k: usize) -> Option<A::T> {
+        if k > n {
+            return Some(ar.zero());
+        }
+        self.rows(ar, n, k, false).map(|mut rows| rows.pop().unwrap().swap_remove(k))
+    }
+}
+
+/// A sequence of numbers a(0), a(1)...
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Sequence {
+    /// Bell numbers, partitions of an n-set, by the Bell triangle.
+    Bell,
+    /// Catalan numbers, by C(n + 1) = C(0) C(n) + ... + C(n) C(0).
+    Catalan,
+    /// Subfactorials, derangements of n items, by D(n) = (n - 1) (D(n - 1) + D(n - 2)).
+    Subfactorial,
+}
+
+impl Sequence {
+    pub(crate) fn terms<A: Arith>(self, ar: &A, n: usize) -> Option<Vec<A::T>> {
+        let mut terms = vec![ar.one()];
+        match self {
+            Sequence::Bell => {
+                let mut row = vec![ar.one()];
+                for _ in 0..n {
+                    let mut next = vec![row.last().unwrap().clone()];
+                    for x in &row {
+                        let y = ar.add(next.last().unwrap(), x)?;
+                        next.push(y);
+                    }
+                    terms.push(next[0].clone());
+                    row = next;
+                }
+            }
+            Sequence::Catalan => {
+                for i in 0..n {
+                    let mut c = ar.zero();
+                    for j in 0..i + 1 {
+                        c = ar.add(&c, &ar.mul(&terms[j], &terms[i - j])?)?;
+                    }
+                    terms.push(c);
+                }
+            }
+            Sequence::Subfactorial => {
+                for i in 1..n + 1 {
+                    let d = if i == 1 {
+                        ar.zero()
+                    } else {
+                        ar.scale(&ar.add(&terms[i - 1], &terms[i - 2])?, i as u64 - 1)?
+                    };
+                    terms.push(d);
+                }
+            }
+        }
+        Some(terms)
+    }
+}
+
+/// Returns n choose k, None if it overflows u64.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// assert_eq!(eu_comb::binomial(10, 3), Some(120));
+/// assert_eq!(eu_comb::binomial(3, 10), Some(0));
+/// assert_eq!(eu_comb::binomial(67, 33), Some(14_226_520_737_620_288_370));
+/// assert_eq!(eu_comb::binomial(68, 34), None);
+/// ```
+pub fn binomial(n: usize, k: usize) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    let mut c: u128 = 1;
+    for i in 0..k {
+        c = c * (n - i) as u128 / (i + 1) as u128;
+        if c > u64::MAX as u128 {
+            return None;
+        }
+    }
+    Some(c as u64)
+}
+
+/// Returns n choose k mod m, from a row of Pascal's triangle in O(n k) time.
+///
+/// Any modulus, panics if m is zero. See `binomial_mod_composite` for large n.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// assert_eq!(eu_comb::binomial_mod(100, 50, 1_000_000_007), 538_992_043);
+/// assert_eq!(eu_comb::binomial_mod(10, 3, 12), 0);
+/// ```
+pub fn binomial_mod(n: usize, k: usize, m: u64) -> u64 {
+    Triangle::Binomial.value(&Modular(m), n, k).unwrap()
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+// inverse of a mod m, a and m coprime
+fn inverse_mod(a: u64, m: u64) -> u64 {
+    let (mut r0, mut r1) = (m as i128, (a % m) as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    t0.rem_euclid(m as i128) as u64
+}
+
+// exponent of p in n!
+fn legendre(mut n: u64, p: u64) -> u64 {
+    let mut e = 0;
+    while n > 0 {
+        n /= p;
+        e += n;
+    }
+    e
+}
+
+/// Returns n choose k mod a prime p by Lucas's theorem.
+///
+/// Multiplies the binomials of the base p digits, each in time linear in the smaller digit.
+/// The result is wrong if p is not prime.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// assert_eq!(eu_comb::binomial_mod_prime(10, 3, 7), 1);
+/// assert_eq!(eu_comb::binomial_mod_prime(1_000_000_000_000_000_000, 13, 13), 6);
+/// assert_eq!(eu_comb::binomial_mod_prime(100, 50, 1_000_000_007), eu_comb::binomial_mod(100, 50, 1_000_000_007));
+/// ```
+pub fn binomial_mod_prime(mut n: u64, mut k: u64, p: u64) -> u64 {
+    let mut res = 1 % p;
+    while k > 0 {
+        let (ni, ki) = (n % p, k % p);
+        if ki > ni {
+            return 0;
+        }
+        let ki = ki.min(ni - ki);
+        let (mut num, mut den) = (1, 1);
+        for i in 0..ki {
+            num = mul_mod(num, ni - i, p);
+            den = mul_mod(den, i + 1, p);
+        }
+        res = mul_mod(res, mul_mod(num, inverse_mod(den, p), p), p);
+        n /= p;
+        k /= p;
+    }
+    res
+}
+
+/// Returns n choose k mod p^e for a prime p, by Granville's generalisation of Lucas's theorem.
+///
+/// Takes time about p^e log n, so p^e should be at most a few million.
+/// The result is wrong if p is not prime.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// assert_eq!(eu_comb::binomial_mod_prime_power(10, 3, 2, 3), 0);
+/// assert_eq!(eu_comb::binomial_mod_prime_power(10, 3, 3, 2), 120 % 9);
+/// assert_eq!(eu_comb::binomial_mod_prime_power(100, 50, 5, 3), eu_comb::binomial_mod(100, 50, 125));
+/// assert_eq!(eu_comb::binomial_mod_prime_power(1_000_000_000_000_000_000, 12, 3, 7), 1647);
+/// assert_eq!(eu_comb::binomial_mod_prime_power(1000, 500, 2, 10), 576);
+/// ```
+pub fn binomial_mod_prime_power(n: u64, k: u64, p: u64, e: u32) -> u64 {
+    let pe = p.pow(e);
+    if k > n {
+        return 0;
+    }
+    // the exponent of p in n choose k is the number of carries adding k and n - k in base p
+    let carries = legendre(n, p) - legendre(k, p) - legendre(n - k, p);
+    if carries >= e as u64 {
+        return 0;
+    }
+    // product of the numbers up to x not divisible by p, mod p^e
+    let coprime_product = |x: u64| (1..x + 1).filter(|i| i % p != 0).fold(1 % pe, |acc, i| mul_mod(acc, i, pe));
+    let block = coprime_product(pe);
+    // n! with the factors of p removed, mod p^e
+    let unit_factorial = |mut x: u64| {
+        let mut res = 1 % pe;
+        while x > 0 {
+            // the block product is 1 or -1
+            let block_power = if (x / pe) % 2 == 1 { block } else { 1 % pe };
+            res = mul_mod(res, mul_mod(block_power, coprime_product(x % pe), pe), pe);
+            x /= p;
+        }
+        res
+    };
+    let den = mul_mod(unit_factorial(k), unit_factorial(n - k), pe);
+    let units = mul_mod(unit_factorial(n), inverse_mod(den, pe), pe);
+    mul_mod(units, p.pow(carries as u32), pe)
+}
+
+/// Returns n choose k mod m for any modulus, combining prime power moduli with the
+/// Chinese remainder theorem.
+///
+/// m is split with `primes::prime_factors`, by trial division in O(√m) time, and each prime
+/// power factor should be at most a few million, see `binomial_mod_prime_power`. Large prime
+/// factors use `binomial_mod_prime`.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// assert_eq!(eu_comb::binomial_mod_composite(100, 50, 1_000_000), eu_comb::binomial_mod(100, 50, 1_000_000));
+/// assert_eq!(eu_comb::binomial_mod_composite(100, 50, 1), 0);
+///
+/// // 142857 = 27 * 11 * 13 * 37
+/// let (n, k) = (1_000_000_000_000_000_007, 12);
+/// let r = eu_comb::binomial_mod_composite(n, k, 142_857);
+/// assert_eq!(r, 134_199);
+/// assert_eq!(r % 27, eu_comb::binomial_mod_prime_power(n, k, 3, 3));
+/// assert_eq!(r % 37, eu_comb::binomial_mod_prime(n, k, 37));
+/// ```
+pub fn binomial_mod_composite(n: u64, k: u64, m: u64) -> u64 {
+    assert!(m != 0, "zero modulus");
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    for p in primes::prime_factors(m as usize) {
+        match factors.last_mut() {
+            Some((q, e)) if *q == p as u64 => *e += 1,
+            _ => factors.push((p as u64, 1)),
+        }
+    }
+    let (mut r, mut modulus) = (0, 1);
+    for (p, e) in factors {
+        let pe = p.pow(e);
+        let ri = if e == 1 { binomial_mod_prime(n, k, p) } else { binomial_mod_prime_power(n, k, p, e) };
+        // x = r mod modulus and x = ri mod pe
+        let t = mul_mod((ri + pe - r % pe) % pe, inverse_mod(modulus % pe, pe), pe);
+        r += modulus * t;
+        modulus *= pe;
+    }
+    r % m
+}
+
+/// Returns T(n, k) of a triangle, None if any entry it depends on overflows u64.
+///
+/// ```
+/// use euler_library::combinatorics::{self as eu_comb, Triangle};
+///
+/// assert_eq!(eu_comb::triangle(Triangle::Stirling1, 5, 2), Some(50));
+/// assert_eq!(eu_comb::triangle(Triangle::Stirling2, 5, 2), Some(15));
+/// assert_eq!(eu_comb::triangle(Triangle::Eulerian, 5, 2), Some(66));
+/// assert_eq!(eu_comb::triangle(Triangle::Stirling2, 30, 40), Some(0));
+/// ```
+pub fn triangle(t: Triangle, n: usize, k: usize) -> Option<u64> {
+    t.value(&Checked::<u64>::new(), n, k)
+}
+
+/// Returns T(n, k) of a triangle mod m. Panics if m is zero.
+///
+/// ```
+/// use euler_library::combinatorics::{self as eu_comb, Triangle};
+///
+/// assert_eq!(eu_comb::triangle_mod(Triangle::Stirling2, 5, 2, 7), 1);
+/// assert_eq!(eu_comb::triangle_mod(Triangle::Stirling1, 100, 50, 1_000_000_007), 768_969_154);
+/// ```
+pub fn triangle_mod(t: Triangle, n: usize, k: usize, m: u64) -> u64 {
+    t.value(&Modular(m), n, k).unwrap()
+}
+
+/// Returns rows 0 to n of a triangle, None if any entry overflows u64.
+///
+/// ```
+/// use euler_library::combinatorics::{self as eu_comb, Triangle};
+///
+/// let pascal = eu_comb::triangle_table(Triangle::Binomial, 4).unwrap();
+/// assert_eq!(pascal[4], [1, 4, 6, 4, 1]);
+///
+/// let eulerian = eu_comb::triangle_table(Triangle::Eulerian, 4).unwrap();
+/// assert_eq!(eulerian, [vec![1], vec![1, 0], vec![1, 1, 0], vec![1, 4, 1, 0], vec![1, 11, 11, 1, 0]]);
+///
+/// assert!(eu_comb::triangle_table(Triangle::Stirling2, 30).is_none());
+/// ```
+pub fn triangle_table(t: Triangle, n: usize) -> Option<Vec<Vec<u64>>> {
+    t.rows(&Checked::<u64>::new(), n, n, true)
+}
+
+/// Returns rows 0 to n of a triangle mod m. Panics if m is zero.
+///
+/// ```
+/// use euler_library::combinatorics::{self as eu_comb, Triangle};
+///
+/// let rows = eu_comb::triangle_table_mod(Triangle::Stirling1, 4, 5);
+/// assert_eq!(rows[4], [0, 1, 1, 1, 1]);
+/// ```
+pub fn triangle_table_mod(t: Triangle, n: usize, m: u64) -> Vec<Vec<u64>> {
+    t.rows(&Modular(m), n, n, true).unwrap()
+}
+
+/// Returns a(n) of a sequence, None if it or an earlier term overflows u64.
+///
+/// ```
+/// use euler_library::combinatorics::{self as eu_comb, Sequence};
+///
+/// assert_eq!(eu_comb::sequence(Sequence::Bell, 10), Some(115_975));
+/// assert_eq!(eu_comb::sequence(Sequence::Catalan, 10), Some(16_796));
+/// assert_eq!(eu_comb::sequence(Sequence::Subfactorial, 10), Some(1_334_961));
+/// assert_eq!(eu_comb::sequence(Sequence::Subfactorial, 21), None);
+/// ```
+pub fn sequence(s: Sequence, n: usize) -> Option<u64> {
+    s.terms(&Checked::<u64>::new(), n).map(|terms| terms[n])
+}
+
+/// Returns a(n) of a sequence mod m. Panics if m is zero.
+///
+/// ```
+/// use euler_library::combinatorics::{self as eu_comb, Sequence};
+///
+/// assert_eq!(eu_comb::sequence_mod(Sequence::Catalan, 1000, 1_000_000_007), 110_961_515);
+/// ```
+pub fn sequence_mod(s: Sequence, n: usize, m: u64) -> u64 {
+    s.terms(&Modular(m), n).unwrap()[n]
+}
+
+/// Returns a(0) to a(n) of a sequence, None if any overflows u64.
+///
+/// ```
+/// use euler_library::combinatorics::{self as eu_comb, Sequence};
+///
+/// assert_eq!(eu_comb::sequence_table(Sequence::Bell, 6), Some(vec![1, 1, 2, 5, 15, 52, 203]));
+/// assert_eq!(eu_comb::sequence_table(Sequence::Subfactorial, 6), Some(vec![1, 0, 1, 2, 9, 44, 265]));
+/// ```
+pub fn sequence_table(s: Sequence, n: usize) -> Option<Vec<u64>> {
+    s.terms(&Checked::<u64>::new(), n)
+}
+
+/// Returns a(0) to a(n) of a sequence mod m. Panics if m is zero.
+///
+/// ```
+/// use euler_library::combinatorics::{self as eu_comb, Sequence};
+///
+/// assert_eq!(eu_comb::sequence_table_mod(Sequence::Catalan, 6, 10), [1, 1, 2, 5, 4, 2, 2]);
+/// ```
+pub fn sequence_table_mod(s: Sequence, n: usize, m: u64) -> Vec<u64> {
+    s.terms(&Modular(m), n).unwrap()
+}
+
+// size hint from a total count, None if it overflows a u64, of which seen are consumed,
+// with no upper bound when the remaining count may be too big for a usize
+fn remaining_hint(total: Option<u64>, seen: usize) -> (usize, Option<usize>) {
+    // an overflowing total leaves more than u64::MAX - seen
+    let left = total.unwrap_or(u64::MAX) - seen as u64;
+    match usize::try_from(left) {
+        Ok(left) if total.is_some() => (left, Some(left)),
+        Ok(left) => (left, None),
+        Err(_) => (usize::MAX, None),
+    }
+}
+
+/// Iterator of the partitions of a slice into blocks, by restricted growth strings.
+pub struct SetPartitions<'a, T: 'a> {
+    xs: &'a [T],
+    // block of each item, a[i] <= 1 + max(a[..i])
+    blocks: Vec<usize>,
+    // max of a[..i + 1]
+    maxes: Vec<usize>,
+    total: Option<u64>,
+    seen: usize,
+    done: bool,
+}
+
+impl<'a, T: Clone> Iterator for SetPartitions<'a, T> {
+    type Item = Vec<Vec<T>>;
+
+    fn next(&mut self) -> Option<Vec<Vec<T>>> {
+        if self.done {
+            return None;
+        }
+        if self.seen > 0 {
+            let n = self.xs.len();
+            // the rightmost item that can move to a later block, all after it go to block 0
+            let i = match (1..n).rev().find(|&i| self.blocks[i] <= self.maxes[i - 1]) {
+                Some(i) => i,
+                None => {
+                    self.done = true;
+                    return None;
+                }
+            };
+            self.blocks[i] += 1;
+            self.maxes[i] = self.maxes[i - 1].max(self.blocks[i]);
+            for j in i + 1..n {
+                self.blocks[j] = 0;
+                self.maxes[j] = self.maxes[i];
+            }
+        }
+        self.seen += 1;
+        let count = self.maxes.last().map_or(0, |&m| m + 1);
+        let mut res = vec![Vec::new(); count];
+        for (x, &b) in self.xs.iter().zip(&self.blocks) {
+            res[b].push(x.clone());
+        }
+        Some(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        remaining_hint(self.total, self.seen)
+    }
+}
+
+/// Returns an iterator of the partitions of xs into nonempty blocks, a Bell number of them.
+///
+/// Blocks are in order of their first item and keep the order of xs.
+/// Partitions come in lexicographic order of their restricted growth strings,
+/// starting with the single block.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// let ps = eu_comb::set_partitions(&[1, 2, 3]).collect::<Vec<_>>();
+/// assert_eq!(ps, [vec![vec![1, 2, 3]],
+///                 vec![vec![1, 2], vec![3]],
+///                 vec![vec![1, 3], vec![2]],
+///                 vec![vec![1], vec![2, 3]],
+///                 vec![vec![1], vec![2], vec![3]]]);
+///
+/// let mut ps = eu_comb::set_partitions(&[1, 2, 3, 4, 5, 6, 7, 8]);
+/// assert_eq!(ps.size_hint(), (4140, Some(4140)));
+/// ps.next();
+/// assert_eq!(ps.size_hint(), (4139, Some(4139)));
+///
+/// // more partitions than a u64 counts have no upper bound
+/// let mut ps = eu_comb::set_partitions(&[0; 30]);
+/// assert_eq!(ps.size_hint(), (usize::MAX, None));
+/// ps.next();
+/// assert_eq!(ps.size_hint(), (usize::MAX - 1, None));
+/// ```
+pub fn set_partitions<T: Clone>(xs: &[T]) -> SetPartitions<'_, T> {
+    SetPartitions {
+        xs,
+        blocks: vec![0; xs.len()],
+        maxes: vec![0; xs.len()],
+        total: sequence(Sequence::Bell, xs.len()),
+        seen: 0,
+        done: false,
+    }
+}
+
+/// Iterator of the compositions of n, ordered sums of parts, in lexicographic order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Compositions {
+    n: usize,
+    min_part: usize,
+    max_part: usize,
+    parts: Vec<usize>,
+    total: Option<u64>,
+    seen: usize,
+    done: bool,
+}
+
+impl Compositions {
+    /// Returns the iterator restricted to parts of at least k, which must be positive.
+    ///
+    /// ```
+    /// use euler_library::combinatorics as eu_comb;
+    ///
+    /// let cs = eu_comb::compositions(7).min_part(2).collect::<Vec<_>>();
+    /// assert_eq!(cs, [vec![2, 2, 3], vec![2, 3, 2], vec![2, 5], vec![3, 2, 2], vec![3, 4], vec![4, 3], vec![5, 2], vec![7]]);
+    /// ```
+    pub fn min_part(mut self, k: usize) -> Compositions {
+        assert!(k > 0, "zero part");
+        self.min_part = k;
+        self.recount()
+    }
+
+    /// Returns the iterator restricted to parts of at most k.
+    ///
+    /// ```
+    /// use euler_library::combinatorics as eu_comb;
+    ///
+    /// // ways to climb 10 stairs taking one or two at a time
+    /// assert_eq!(eu_comb::compositions(10).max_part(2).count(), 89);
+    /// ```
+    pub fn max_part(mut self, k: usize) -> Compositions {
+        self.max_part = k;
+        self.recount()
+    }
+
+    fn recount(mut self) -> Compositions {
+        let (lo, hi) = (self.min_part, self.max_part);
+        // ways[r] counts compositions of r, saturating past u64
+        let mut ways = vec![0u64; self.n + 1];
+        ways[0] = 1;
+        for r in 1..self.n + 1 {
+            let parts = lo..hi.min(r) + 1;
+            ways[r] = parts.fold(0u64, |acc, v| acc.saturating_add(ways[r - v]));
+        }
+        self.total = Some(ways[self.n]).filter(|&t| t < u64::MAX);
+        self
+    }
+
+    // true if r is a sum of parts within the bounds
+    fn feasible(&self, r: usize) -> bool {
+        r == 0 || (self.max_part > 0 && r.div_ceil(self.max_part) <= r / self.min_part)
+    }
+
+    // appends the lexicographically smallest parts summing to r
+    fn fill(&mut self, mut r: usize) {
+        while r > 0 {
+            let v = (self.min_part..self.max_part.min(r) + 1).find(|&v| self.feasible(r - v)).unwrap();
+            self.parts.push(v);
+            r -= v;
+        }
+    }
+}
+
+impl Iterator for Compositions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.done {
+            return None;
+        }
+        if self.seen == 0 {
+            if !self.feasible(self.n) {
+                self.done = true;
+                return None;
+            }
+            let n = self.n;
+            self.fill(n);
+        } else {
+            // raise the rightmost part that can be raised, then refill the rest as low as possible
+            let mut r = 0;
+            loop {
+                let part = match self.parts.pop() {
+                    Some(part) => part,
+                    None => {
+                        self.done = true;
+                        return None;
+                    }
+                };
+                r += part;
+                if let Some(v) = (part + 1..self.max_part.min(r) + 1).find(|&v| self.feasible(r - v)) {
+                    self.parts.push(v);
+                    self.fill(r - v);
+                    break;
+                }
+            }
+        }
+        self.seen += 1;
+        Some(self.parts.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        remaining_hint(self.total, self.seen)
+    }
+}
+
+/// Returns an iterator of the compositions of n, the ordered ways of writing n as a sum of
+/// positive parts, in lexicographic order.
+///
+/// `min_part` and `max_part` bound the parts. There are 2^(n - 1) compositions of n > 0.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// let cs = eu_comb::compositions(4).collect::<Vec<_>>();
+/// assert_eq!(cs, [vec![1, 1, 1, 1], vec![1, 1, 2], vec![1, 2, 1], vec![1, 3],
+///                 vec![2, 1, 1], vec![2, 2], vec![3, 1], vec![4]]);
+/// assert_eq!(eu_comb::compositions(20).size_hint(), (1 << 19, Some(1 << 19)));
+/// assert_eq!(eu_comb::compositions(0).collect::<Vec<_>>(), [Vec::<usize>::new()]);
+/// ```
+pub fn compositions(n: usize) -> Compositions {
+    Compositions { n, min_part: 1, max_part: n, parts: Vec::new(), total: None, seen: 0, done: false }.recount()
+}
+
+/// Iterator of the derangements of a slice.
+pub struct Derangements<'a, T: 'a> {
+    xs: &'a [T],
+    indices: Vec<usize>,
+    total: Option<u64>,
+    seen: usize,
+    done: bool,
+}
+
+impl<'a, T> Derangements<'a, T> {
+    // true if position v may go at slot p, leaving the later slots a derangement
+    fn allowed(&self, p: usize, v: usize) -> bool {
+        let n = self.xs.len();
+        if v == p || self.indices[..p].contains(&v) {
+            return false;
+        }
+        // the one position left for the last slot must not be its own
+        p + 2 != n || self.indices[..p].contains(&(n - 1)) || v == n - 1
+    }
+
+    // fills slots from p with the smallest allowed positions
+    fn fill(&mut self, p: usize) {
+        for q in p..self.xs.len() {
+            self.indices[q] = (0..self.xs.len()).find(|&v| self.allowed(q, v)).unwrap();
+        }
+    }
+}
+
+impl<'a, T: Clone> Iterator for Derangements<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let n = self.xs.len();
+        if self.seen == 0 {
+            if n == 1 {
+                self.done = true;
+                return None;
+            }
+            self.fill(0);
+        } else {
+            let i = (0..n).rev().find_map(|i| {
+                (self.indices[i] + 1..n).find(|&v| self.allowed(i, v)).map(|v| (i, v))
+            });
+            match i {
+                Some((i, v)) => {
+                    self.indices[i] = v;
+                    self.fill(i + 1);
+                }
+                None => {
+                    self.done = true;
+                    return None;
+                }
+            }
+        }
+        self.seen += 1;
+        Some(self.indices.iter().map(|&i| self.xs[i].clone()).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        remaining_hint(self.total, self.seen)
+    }
+}
+
+/// Returns an iterator of the derangements of xs, the permutations leaving no item in its
+/// position, in lexicographic order of positions.
+///
+/// Items are told apart by position, as in `permutations`.
+///
+/// ```
+/// use euler_library::combinatorics as eu_comb;
+///
+/// let ds = eu_comb::derangements(&['a', 'b', 'c', 'd']).map(|d| d.into_iter().collect::<String>());
+/// assert_eq!(ds.collect::<Vec<_>>(), ["badc", "bcda", "bdac", "cadb", "cdab", "cdba", "dabc", "dcab", "dcba"]);
+/// assert_eq!(eu_comb::derangements(&[0; 9]).size_hint(), (133_496, Some(133_496)));
+/// assert_eq!(eu_comb::derangements(&[0; 21]).size_hint(), (usize::MAX, None));
+/// assert_eq!(eu_comb::derangements(&[1]).count(), 0);
+/// ```
+pub fn derangements<T: Clone>(xs: &[T]) -> Derangements<'_, T> {
+    Derangements {
+        xs,
+        indices: vec![0; xs.len()],
+        total: sequence(Sequence::Subfactorial, xs.len()),
+        seen: 0,
+        done: false,
+    }
+}
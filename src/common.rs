@@ -13,6 +13,8 @@
 //! assert_eq!(eu::perms_with_reps(2, &vec![1, 2, 3]), res);
 //! ```
 
+use combinatorics;
+use continued_fractions;
 use std::fmt::Debug;
 use std::iter;
 use std::iter::{Repeat, Take};
@@ -32,7 +34,7 @@ pub fn divisor_sum(n: usize) -> usize {
         _ => {
             let max = ((n as f64).sqrt() + 1.0) as usize;
             (2..max).fold(1, |acc, x| {
-                if n % x == 0 {
+                if n.is_multiple_of(x) {
                     let d = n / x;
                     if d == x { acc + d } else { acc + x + d }
                 } else {
@@ -68,6 +70,7 @@ pub fn divisor_sum_list(limit: usize) -> Vec<usize> {
 /// Normal definition is that an n-digit number is pandigital if it makes
 /// use of all the digits 1 to n exactly once.
 /// This function checks if pandigital from start to n exactly once.
+/// See `pandigital::is_pandigital` for numbers and any digit range.
 ///
 /// ```
 /// use euler_library::common as eu;
@@ -96,7 +99,7 @@ pub fn is_pandigital(string: String, start: usize) -> bool {
 /// assert_eq!(eu::sum_of_digits("123".to_string()), 6);
 /// ```
 pub fn sum_of_digits(s: String) -> usize {
-    s.chars().into_iter().fold(0, |acc, x| acc + x.to_digit(10).unwrap()) as usize
+    s.chars().fold(0, |acc, x| acc + x.to_digit(10).unwrap()) as usize
 }
 
 /// Returns n as a vector of bytes.
@@ -134,9 +137,11 @@ pub fn from_bytes<T>(vec: &[u8]) -> Result<T, T::Err>
 /// use euler_library::common as eu;
 ///
 /// assert_eq!(eu::to_digits(123), [1, 2, 3]);
+/// assert_eq!(eu::to_digits(0), [0]);
 /// ```
 pub fn to_digits(mut n: usize) -> Vec<usize> {
-    let mut res = Vec::new();
+    let mut res = vec![n % 10];
+    n /= 10;
     while n != 0 {
         res.push(n % 10);
         n /= 10
@@ -191,24 +196,252 @@ pub fn is_palindrome<T: ToString>(n: T) -> bool {
 ///
 /// ```
 pub fn factorial(n: usize) -> usize {
-    (1..n + 1).fold(1, |p, n| p * n)
+    (1..n + 1).product()
+}
+
+/// Rearranges xs into the next larger permutation, returning false if it is already the largest.
+///
+/// Equal items are not told apart, so each distinct arrangement comes once.
+/// The largest permutation is left unchanged.
+///
+/// ```
+/// use euler_library::common as eu;
+///
+/// let mut xs = [1, 2, 2, 3];
+/// let mut count = 1;
+/// while eu::next_permutation(&mut xs) {
+///     count += 1;
+/// }
+/// assert_eq!(count, 12);
+/// assert_eq!(xs, [3, 2, 2, 1]);
+///
+/// let mut word = *b"hello";
+/// assert!(eu::next_permutation(&mut word));
+/// assert_eq!(&word, b"helol");
+/// ```
+pub fn next_permutation<T: Ord>(xs: &mut [T]) -> bool {
+    let i = match (1..xs.len()).rev().find(|&i| xs[i - 1] < xs[i]) {
+        Some(i) => i - 1,
+        None => return false,
+    };
+    let j = (i + 1..xs.len()).rev().find(|&j| xs[j] > xs[i]).unwrap();
+    xs.swap(i, j);
+    xs[i + 1..].reverse();
+    true
+}
+
+/// Rearranges xs into the next smaller permutation, returning false if it is already the smallest.
+///
+/// Equal items are not told apart. The smallest permutation is left unchanged.
+///
+/// ```
+/// use euler_library::common as eu;
+///
+/// let mut xs = [3, 1, 1];
+/// assert!(eu::prev_permutation(&mut xs));
+/// assert_eq!(xs, [1, 3, 1]);
+/// assert!(eu::prev_permutation(&mut xs));
+/// assert_eq!(xs, [1, 1, 3]);
+/// assert!(!eu::prev_permutation(&mut xs));
+/// ```
+pub fn prev_permutation<T: Ord>(xs: &mut [T]) -> bool {
+    let i = match (1..xs.len()).rev().find(|&i| xs[i - 1] > xs[i]) {
+        Some(i) => i - 1,
+        None => return false,
+    };
+    let j = (i + 1..xs.len()).rev().find(|&j| xs[j] < xs[i]).unwrap();
+    xs.swap(i, j);
+    xs[i + 1..].reverse();
+    true
+}
+
+/// Returns the index of xs among the permutations of its items in increasing order, from 0.
+///
+/// Reads the factorial number system digits of the rank. Items must be distinct,
+/// see `multiset_permutation_rank` otherwise. Panics if the rank overflows usize.
+///
+/// ```
+/// use euler_library::common as eu;
+///
+/// assert_eq!(eu::permutation_rank(&[1, 2, 3]), 0);
+/// assert_eq!(eu::permutation_rank(&['c', 'a', 'b']), 4);
+/// assert_eq!(eu::permutation_rank(&[2, 7, 8, 3, 9, 1, 5, 4, 6, 0]), 999_999);
+/// ```
+pub fn permutation_rank<T: Ord>(xs: &[T]) -> usize {
+    let n = xs.len();
+    let mut rank: usize = 0;
+    for i in 0..n {
+        let smaller_after = xs[i + 1..].iter().filter(|x| *x < &xs[i]).count();
+        rank = rank.checked_mul(n - i)
+            .and_then(|r| r.checked_add(smaller_after))
+            .expect("permutation rank overflows usize");
+    }
+    rank
+}
+
+/// Returns the permutation of the items of xs at index i in increasing order, from 0.
+///
+/// Builds the factorial number system digits of i. Items must be distinct,
+/// see `nth_multiset_permutation` otherwise. Returns None if i is not below the number
+/// of permutations.
+///
+/// ```
+/// use euler_library::common as eu;
+///
+/// // Problem 24: the millionth permutation of 0 to 9
+/// let p = eu::nth_permutation(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], 999_999).unwrap();
+/// assert_eq!(p, [2, 7, 8, 3, 9, 1, 5, 4, 6, 0]);
+///
+/// assert_eq!(eu::nth_permutation(&['b', 'a', 'c'], 4), Some(vec!['c', 'a', 'b']));
+/// assert_eq!(eu::nth_permutation(&[1, 2, 3], 6), None);
+/// ```
+pub fn nth_permutation<T: Ord + Clone>(xs: &[T], i: usize) -> Option<Vec<T>> {
+    let mut items = xs.to_vec();
+    items.sort();
+    // factorials of 0..n, saturating when larger than any usize index
+    let fact = (0..items.len() + 1)
+        .scan(1usize, |f, k| {
+            let current = *f;
+            *f = f.saturating_mul(k + 1);
+            Some(current)
+        })
+        .collect::<Vec<_>>();
+    if i >= fact[items.len()] {
+        return None;
+    }
+    let mut i = i;
+    let mut res = Vec::with_capacity(items.len());
+    while !items.is_empty() {
+        let f = fact[items.len() - 1];
+        res.push(items.remove(i / f));
+        i %= f;
+    }
+    Some(res)
+}
+
+// distinct items of xs in increasing order with their counts
+fn item_counts<T: Ord + Clone>(xs: &[T]) -> Vec<(T, usize)> {
+    let mut items = xs.to_vec();
+    items.sort();
+    let mut counts: Vec<(T, usize)> = Vec::new();
+    for x in items {
+        match counts.last_mut() {
+            Some((y, c)) if *y == x => *c += 1,
+            _ => counts.push((x, 1)),
+        }
+    }
+    counts
+}
+
+// number of distinct arrangements of the counts, None if over usize
+fn arrangements(counts: &[usize]) -> Option<usize> {
+    let mut total: u128 = 1;
+    let mut len = 0;
+    for &c in counts {
+        for j in 1..c + 1 {
+            len += 1;
+            total = total * len / j as u128;
+            if total > usize::MAX as u128 {
+                return None;
+            }
+        }
+    }
+    Some(total as usize)
+}
+
+/// Returns the index of xs among the distinct arrangements of its items in increasing order, from 0.
+///
+/// Repeated items are allowed. Panics if the rank overflows usize.
+///
+/// ```
+/// use euler_library::common as eu;
+///
+/// assert_eq!(eu::multiset_permutation_rank(&[1, 1, 2, 2]), 0);
+/// assert_eq!(eu::multiset_permutation_rank(&[2, 1, 2, 1]), 4);
+/// assert_eq!(eu::multiset_permutation_rank(&[2, 2, 1, 1]), 5);
+/// assert_eq!(eu::multiset_permutation_rank(b"mississippi"), 13_736);
+/// ```
+pub fn multiset_permutation_rank<T: Ord + Clone>(xs: &[T]) -> usize {
+    let mut counts = item_counts(xs);
+    let mut rank: usize = 0;
+    for x in xs {
+        let pos = counts.iter().position(|(y, _)| y == x).unwrap();
+        let mut cs = counts.iter().map(|&(_, c)| c).collect::<Vec<_>>();
+        // arrangements starting with each smaller item
+        for v in 0..pos {
+            cs[v] -= 1;
+            let ways = arrangements(&cs).expect("permutation rank overflows usize");
+            rank = rank.checked_add(ways).expect("permutation rank overflows usize");
+            cs[v] += 1;
+        }
+        counts[pos].1 -= 1;
+        if counts[pos].1 == 0 {
+            counts.remove(pos);
+        }
+    }
+    rank
+}
+
+/// Returns the distinct arrangement of the items of xs at index i in increasing order, from 0.
+///
+/// Repeated items are allowed. Returns None if i is not below the number of arrangements.
+///
+/// ```
+/// use euler_library::common as eu;
+///
+/// assert_eq!(eu::nth_multiset_permutation(&[2, 1, 1, 2], 4), Some(vec![2, 1, 2, 1]));
+/// assert_eq!(eu::nth_multiset_permutation(&[2, 1, 1, 2], 6), None);
+///
+/// let word = eu::nth_multiset_permutation(b"mississippi", 13_736).unwrap();
+/// assert_eq!(word, b"mississippi");
+/// ```
+pub fn nth_multiset_permutation<T: Ord + Clone>(xs: &[T], i: usize) -> Option<Vec<T>> {
+    let mut counts = item_counts(xs);
+    let cs = counts.iter().map(|&(_, c)| c).collect::<Vec<_>>();
+    if arrangements(&cs).is_some_and(|total| i >= total) {
+        return None;
+    }
+    let mut i = i;
+    let mut res = Vec::with_capacity(xs.len());
+    while !counts.is_empty() {
+        let mut cs = counts.iter().map(|&(_, c)| c).collect::<Vec<_>>();
+        for v in 0..counts.len() {
+            cs[v] -= 1;
+            // over usize is more than any index
+            let ways = arrangements(&cs).unwrap_or(usize::MAX);
+            cs[v] += 1;
+            if i < ways {
+                res.push(counts[v].0.clone());
+                counts[v].1 -= 1;
+                if counts[v].1 == 0 {
+                    counts.remove(v);
+                }
+                break;
+            }
+            i -= ways;
+        }
+    }
+    Some(res)
 }
 
 /// Returns permutations k chosen from xs, odered, repetition allowed.
 ///
-/// Iterative solution.
+/// Collects `combinatorics::cartesian_power`, use it directly to avoid building every permutation.
 ///
 /// ```
 /// use euler_library::common as eu;
 ///
 /// assert_eq!(eu::perms_with_reps(2, &vec![1,2]), [[1,1], [1,2], [2,1], [2,2]]);
+/// assert_eq!(eu::perms_with_reps(2, &vec![1,1]), [[1,1], [1,1], [1,1], [1,1]]);
 ///
 /// ```
 pub fn perms_with_reps<T>(k: usize, xs: &[T]) -> Vec<Vec<T>>
     where T: Clone
 {
-    let vec = replicate(k, xs.to_vec()).collect::<Vec<_>>();
-    cartesian_product(&vec)
+    if k == 0 {
+        return vec![];
+    }
+    combinatorics::cartesian_power(xs, k).collect()
 }
 
 /// Returns and iterator of length n of repeated values of elt
@@ -222,6 +455,7 @@ pub fn perms_with_reps<T>(k: usize, xs: &[T]) -> Vec<Vec<T>>
 /// let repeated = eu::replicate(3, "abc").collect::<String>();
 /// assert_eq!(repeated, "abcabcabc");
 /// ```
+#[allow(clippy::manual_repeat_n)]
 pub fn replicate<T>(n: usize, elt: T) -> Take<Repeat<T>>
     where T: Clone
 {
@@ -230,7 +464,7 @@ pub fn replicate<T>(n: usize, elt: T) -> Take<Repeat<T>>
 
 /// Returns the cartesian product of a Vec of Vec of T.
 ///
-/// Recursive solution.
+/// Collects `combinatorics::cartesian_product`, use it directly for a lazy iterator.
 ///
 /// ```
 /// use euler_library::common as eu;
@@ -240,60 +474,40 @@ pub fn replicate<T>(n: usize, elt: T) -> Take<Repeat<T>>
 ///
 /// ```
 pub fn cartesian_product<T: Clone>(lists: &[Vec<T>]) -> Vec<Vec<T>> {
-    fn partial_cartesian<T: Clone>(a: Vec<Vec<T>>, b: &[T]) -> Vec<Vec<T>> {
-        a.into_iter()
-            .flat_map(|xs| {
-                b.iter()
-                    .cloned()
-                    .map(|y| {
-                        let mut temp = xs.clone();
-                        temp.push(y);
-                        temp
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect()
-    }
-
-    match lists.split_first() {
-        Some((first, rest)) => {
-            let init = first.into_iter()
-                .clone()
-                .map(|n| vec![n.clone()])
-                .collect::<Vec<Vec<T>>>();
-            rest.iter()
-                .clone()
-                .fold(init, |vec, list| partial_cartesian(vec, list))
-        }
-        None => vec![],
+    if lists.is_empty() {
+        return vec![];
     }
+    combinatorics::cartesian_product(lists).collect()
 }
 
 /// Returns permutations k chosen from xs, odered, no repetition.
 ///
-/// Recursive solution.
+/// Recursive solution. Items are told apart by value, so an item equal to one already chosen
+/// is skipped, and for k = 1 the items are returned in the order given. See
+/// `combinatorics::k_permutations` for a lazy iterator telling items apart by position.
 ///
 /// ```
 /// use euler_library::common as eu;
 ///
 /// assert_eq!(eu::perms_without_reps_recur(2, &[1,2]), [[1,2], [2,1]]);
+/// assert_eq!(eu::perms_without_reps_recur(2, &[1,1,2]), [[1,2], [1,2], [2,1], [2,1]]);
+/// assert_eq!(eu::perms_without_reps_recur(1, &[2,1]), [[2], [1]]);
 ///
 /// ```
 pub fn perms_without_reps_recur<T>(k: usize, xs: &[T]) -> Vec<Vec<T>>
     where T: Clone + PartialEq + Ord + Debug
 {
     match k {
-        0 => return vec![vec![]],
+        0 => vec![vec![]],
         1 => xs.iter().map(|x| vec![x.clone()]).collect(),
         _ => {
+            let ts = perms_without_reps_recur(k - 1, xs);
             let mut list: Vec<Vec<T>> = Vec::new();
             for x in xs {
-                let ts = perms_without_reps_recur(k - 1, xs);
-                for mut t in ts {
-                    if !t.contains(x) {
-                        t.push(x.clone());
-                        list.push(t);
-                    }
+                for t in ts.iter().filter(|t| !t.contains(x)) {
+                    let mut t = t.clone();
+                    t.push(x.clone());
+                    list.push(t);
                 }
             }
             list.sort();
@@ -308,13 +522,15 @@ pub fn perms_without_reps_recur<T>(k: usize, xs: &[T]) -> Vec<Vec<T>>
 ///
 ///  for (i,vi) 0..2 { for (j,vj) in i..2 { for (k,vk) in j..2 { list.push(vi, vj, vk) } } }
 ///
-/// Recursive solution.
+/// Recursive solution. For k = 1 the items are returned in the order given, otherwise sorted.
+/// See `combinatorics::cartesian_power` for a lazy iterator.
 ///
 /// ```
 /// use euler_library::common as eu;
 ///
 /// let res = [[1,1,1], [1,1,2], [1,2,1], [1,2,2], [2,1,1], [2,1,2], [2,2,1], [2,2,2]];
 /// assert_eq!(eu::k_nested_recur(3, &[1,2]), res);
+/// assert_eq!(eu::k_nested_recur(1, &[2,1]), [[2], [1]]);
 ///
 /// let xss = eu::k_nested_recur(8, &["red", "green", "blue", "orange"]);
 /// assert_eq!(xss.len(), 65536);
@@ -324,13 +540,14 @@ pub fn k_nested_recur<T>(k: usize, xs: &[T]) -> Vec<Vec<T>>
     where T: Clone + PartialEq + Ord + Debug
 {
     match k {
-        0 => return vec![vec![]],
+        0 => vec![vec![]],
         1 => xs.iter().map(|x| vec![x.clone()]).collect(),
         _ => {
+            let ts = k_nested_recur(k - 1, xs);
             let mut list: Vec<Vec<T>> = Vec::new();
             for x in xs {
-                let ts = k_nested_recur(k - 1, xs);
-                for mut t in ts {
+                for t in &ts {
+                    let mut t = t.clone();
                     t.push(x.clone());
                     list.push(t);
                 }
@@ -376,6 +593,9 @@ pub fn sqrt_terms(n: usize) -> Option<(usize, Vec<usize>)> {
 ///
 /// Form of continued fraction: (a0, [t1, t2, t3, ....])
 ///
+/// Panics if the result overflows usize, see `continued_fractions::convergents`
+/// for lazy convergents of any number of terms.
+///
 /// ```
 /// use euler_library::common as eu;
 ///
@@ -390,18 +610,13 @@ pub fn sqrt_terms(n: usize) -> Option<(usize, Vec<usize>)> {
 ///
 /// assert_eq!(eu::continued_fraction(a0, ts), (154451, 42837));
 /// ```
-pub fn continued_fraction(a0: usize, mut xs: Vec<usize>) -> (usize, usize) {
-    fn go(n: usize, d: usize, mut xs: Vec<usize>) -> (usize, usize) {
-        if xs.is_empty() {
-            return (n, d);
-        }
-        let a = xs.pop().unwrap();
-        go(a * n + d, n, xs)
+pub fn continued_fraction(a0: usize, xs: Vec<usize>) -> (usize, usize) {
+    let n = xs.len();
+    let (k, (p, q)) = continued_fractions::convergents(iter::once(a0).chain(xs)).enumerate().last().unwrap();
+    if k < n || p > usize::MAX as u128 || q > usize::MAX as u128 {
+        panic!("continued fraction overflows usize");
     }
-
-    xs.insert(0, a0);
-    let a = xs.pop().unwrap();
-    go(a, 1, xs)
+    (p as usize, q as usize)
 }
 
 /// Returns true if a and b are permutations of each other.
@@ -468,9 +683,9 @@ pub fn phis(d: usize) -> Vec<usize> {
 /// assert_eq!(eu::accumulate(&[1,1,1,1,1]), [1,2,3,4,5]);
 /// ```
 pub fn accumulate(xs: &[usize]) -> Vec<usize> {
-    xs.into_iter()
+    xs.iter()
         .scan(0, |state, x| {
-            *state = *state + x;
+            *state += x;
             Some(*state)
         })
         .collect()
@@ -0,0 +1,140 @@
+//! High-precision mathematical constants as `BigDecimal`s.
+//!
+//! Each function returns the constant truncated to digits decimal places.
+//! π, e and ln 2 are summed by binary splitting, φ comes from an integer square root.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::constants as eu_constants;
+//!
+//! assert_eq!(eu_constants::pi(30).to_string(), "3.141592653589793238462643383279");
+//! assert_eq!(eu_constants::e(30).to_string(), "2.718281828459045235360287471352");
+//!
+//! // digit distribution of the first thousand decimals of pi
+//! let digits = eu_constants::pi(1000).fraction_digits();
+//! assert_eq!(digits.bytes().filter(|&b| b == b'7').count(), 95);
+//! ```
+
+extern crate num;
+use self::num::{BigInt, One, pow};
+use self::num::bigint::ToBigUint;
+
+use big;
+use decimal::BigDecimal;
+
+// extra digits first computed before truncating, doubled until the truncation is certain
+const GUARD: usize = 10;
+
+// Binary splitting of S = sum over n in lo..hi of a(n) / b(n) * p(lo)...p(n) / (q(lo)...q(n)),
+// where term(n) = (a, b, p, q). Returns (P, Q, B, T) with S = T / (B Q).
+fn split<F>(lo: usize, hi: usize, term: &F) -> (BigInt, BigInt, BigInt, BigInt)
+    where F: Fn(usize) -> (BigInt, BigInt, BigInt, BigInt)
+{
+    if hi - lo == 1 {
+        let (a, b, p, q) = term(lo);
+        let t = a * &p;
+        return (p, q, b, t);
+    }
+    let mid = (lo + hi) / 2;
+    let (pl, ql, bl, tl) = split(lo, mid, term);
+    let (pr, qr, br, tr) = split(mid, hi, term);
+    let t = &br * &qr * tl + &bl * &pl * tr;
+    (pl * pr, ql * qr, bl * br, t)
+}
+
+// truncates a positive irrational x to digits places, given mantissas within 2 of x 10^scale
+fn truncate<F: Fn(usize) -> BigInt>(digits: usize, mantissa: F) -> BigDecimal {
+    let mut guard = GUARD;
+    loop {
+        let m = mantissa(digits + guard);
+        let unit = ten_pow(guard);
+        let (lo, hi) = ((&m - 2) / &unit, (&m + 2) / &unit);
+        if lo == hi {
+            return BigDecimal::new(lo, digits);
+        }
+        guard *= 2;
+    }
+}
+
+fn ten_pow(n: usize) -> BigInt {
+    pow(BigInt::from(10), n)
+}
+
+/// Returns π to digits decimal places, by the Chudnovsky series.
+///
+/// ```
+/// use euler_library::constants as eu_constants;
+///
+/// assert_eq!(eu_constants::pi(0).to_string(), "3");
+/// assert_eq!(eu_constants::pi(5).to_string(), "3.14159");
+/// // the Feynman point, six nines from decimal 762
+/// assert_eq!(&eu_constants::pi(770).fraction_digits()[761..767], "999999");
+/// ```
+pub fn pi(digits: usize) -> BigDecimal {
+    truncate(digits, |scale| {
+        let terms = scale / 14 + 2;
+        let (_, q, b, t) = split(0, terms, &|n| {
+            let n_big = BigInt::from(n);
+            let a = BigInt::from(13_591_409) + BigInt::from(545_140_134) * &n_big;
+            if n == 0 {
+                return (a, One::one(), One::one(), One::one());
+            }
+            let p = -(BigInt::from(6 * n - 5) * BigInt::from(2 * n - 1) * BigInt::from(6 * n - 1));
+            let q = BigInt::from(10_939_058_860_032_000u64) * &n_big * &n_big * &n_big;
+            (a, One::one(), p, q)
+        });
+        let sqrt_10005 = big::isqrt(&(10_005.to_biguint().unwrap() * pow(10.to_biguint().unwrap(), 2 * scale)));
+        BigInt::from(426_880) * BigInt::from(sqrt_10005) * b * q / t
+    })
+}
+
+/// Returns e to digits decimal places, by the series of 1 / n!.
+///
+/// ```
+/// use euler_library::constants as eu_constants;
+///
+/// assert_eq!(eu_constants::e(10).to_string(), "2.7182818284");
+/// ```
+pub fn e(digits: usize) -> BigDecimal {
+    truncate(digits, |scale| {
+        // smallest n with log10(n!) > scale + 1
+        let (mut terms, mut log) = (1, 0.0);
+        while log <= scale as f64 + 1.0 {
+            terms += 1;
+            log += (terms as f64).log10();
+        }
+        let (_, q, b, t) = split(0, terms, &|n| {
+            let q = BigInt::from(n.max(1));
+            (One::one(), One::one(), One::one(), q)
+        });
+        t * ten_pow(scale) / (b * q)
+    })
+}
+
+/// Returns the natural logarithm of 2 to digits decimal places, by the series of 1 / (n 2^n).
+///
+/// ```
+/// use euler_library::constants as eu_constants;
+///
+/// assert_eq!(eu_constants::ln_2(30).to_string(), "0.693147180559945309417232121458");
+/// ```
+pub fn ln_2(digits: usize) -> BigDecimal {
+    truncate(digits, |scale| {
+        let terms = scale * 10 / 3 + 2;
+        let (_, q, b, t) = split(0, terms, &|n| (One::one(), BigInt::from(n + 1), One::one(), BigInt::from(2)));
+        t * ten_pow(scale) / (b * q)
+    })
+}
+
+/// Returns the golden ratio φ = (1 + √5) / 2 to digits decimal places.
+///
+/// ```
+/// use euler_library::constants as eu_constants;
+///
+/// assert_eq!(eu_constants::phi(30).to_string(), "1.618033988749894848204586834365");
+/// ```
+pub fn phi(digits: usize) -> BigDecimal {
+    let sqrt_5 = big::isqrt(&(5.to_biguint().unwrap() * pow(10.to_biguint().unwrap(), 2 * digits)));
+    BigDecimal::new((ten_pow(digits) + BigInt::from(sqrt_5)) / BigInt::from(2), digits)
+}
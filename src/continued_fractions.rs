@@ -0,0 +1,300 @@
+//! Continued fractions.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::common as eu;
+//! use euler_library::continued_fractions as eu_cf;
+//! use std::iter;
+//!
+//! // convergents of sqrt(2) = [1; 2, 2, 2, ...]
+//! let (a0, period) = eu::sqrt_terms(2).unwrap();
+//! let quotients = iter::once(a0).chain(period.into_iter().cycle());
+//! let cs = eu_cf::convergents(quotients).take(5).collect::<Vec<_>>();
+//! assert_eq!(cs, [(1, 1), (3, 2), (7, 5), (17, 12), (41, 29)]);
+//!
+//! // the same from the expansion of sqrt(2)
+//! let root_2 = eu_cf::ContinuedFraction::quadratic(0, 1, 2, 1);
+//! assert_eq!((root_2.pre_period.clone(), root_2.period.clone()), (vec![1], vec![2]));
+//! assert_eq!(root_2.convergents().nth(4), Some((41, 29)));
+//! ```
+
+use arith::{Arith, Big, Checked};
+
+use std::collections::HashMap;
+
+/// Iterator of the convergents (p_k, q_k) of a continued fraction [a0; a1, a2, ...].
+///
+/// Uses the forward recurrence p_k = a_k p_(k-1) + p_(k-2), likewise for q_k.
+/// Ends with the partial quotients, or before the first convergent that overflows.
+pub struct Convergents<I, A: Arith> {
+    quotients: I,
+    ar: A,
+    p: (A::T, A::T),
+    q: (A::T, A::T),
+    done: bool,
+}
+
+impl<I, A> Iterator for Convergents<I, A>
+    where I: Iterator<Item = usize>,
+          A: Arith
+{
+    type Item = (A::T, A::T);
+
+    fn next(&mut self) -> Option<(A::T, A::T)> {
+        if self.done {
+            return None;
+        }
+        let ar = &self.ar;
+        let (p, q) = (&self.p, &self.q);
+        let next = self.quotients.next().and_then(|a| {
+            let p = ar.add(&ar.scale(&p.0, a as u64)?, &p.1)?;
+            let q = ar.add(&ar.scale(&q.0, a as u64)?, &q.1)?;
+            Some((p, q))
+        });
+        match next {
+            Some((p, q)) => {
+                self.p = (p.clone(), self.p.0.clone());
+                self.q = (q.clone(), self.q.0.clone());
+                Some((p, q))
+            }
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+fn new_convergents<I, A>(ar: A, quotients: I) -> Convergents<I::IntoIter, A>
+    where I: IntoIterator<Item = usize>,
+          A: Arith
+{
+    Convergents {
+        quotients: quotients.into_iter(),
+        p: (ar.one(), ar.zero()),
+        q: (ar.zero(), ar.one()),
+        ar,
+        done: false,
+    }
+}
+
+/// Returns an iterator of the `u128` convergents of partial quotients [a0; a1, a2, ...].
+///
+/// The iterator ends before a convergent that would overflow.
+///
+/// ```
+/// use euler_library::continued_fractions as eu_cf;
+/// use std::iter;
+///
+/// // golden ratio [1; 1, 1, ...], convergents are ratios of Fibonacci numbers
+/// let cs = eu_cf::convergents(iter::repeat(1)).collect::<Vec<_>>();
+/// assert_eq!(cs[4], (8, 5));
+/// assert_eq!(cs.len(), 185);
+/// ```
+pub fn convergents<I>(quotients: I) -> Convergents<I::IntoIter, Checked<u128>>
+    where I: IntoIterator<Item = usize>
+{
+    new_convergents(Checked::new(), quotients)
+}
+
+/// Returns an iterator of the `BigUint` convergents of partial quotients [a0; a1, a2, ...].
+///
+/// ```
+/// use euler_library::common as eu;
+/// use euler_library::continued_fractions as eu_cf;
+/// use std::iter;
+///
+/// // Problem 65: the 100th convergent of e = [2; 1, 2, 1, 1, 4, 1, 1, 6, ...]
+/// let quotients = iter::once(2).chain((1..).flat_map(|k| vec![1, 2 * k, 1]));
+/// let (p, _) = eu_cf::big_convergents(quotients).nth(99).unwrap();
+/// assert_eq!(eu::sum_of_digits(p.to_string()), 272);
+/// ```
+pub fn big_convergents<I>(quotients: I) -> Convergents<I::IntoIter, Big>
+    where I: IntoIterator<Item = usize>
+{
+    new_convergents(Big, quotients)
+}
+
+/// A continued fraction [a0; a1, a2, ...] as terms before a period and the repeating period.
+///
+/// Term j of the k-th repeat of the period (from 0) is `period[j] + k * step[j]`,
+/// so an all zero or empty `step` is an ordinary periodic fraction and a nonzero one gives
+/// patterned expansions such as e. An empty period is a finite fraction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContinuedFraction {
+    pub pre_period: Vec<usize>,
+    pub period: Vec<usize>,
+    pub step: Vec<usize>,
+}
+
+impl ContinuedFraction {
+    /// Returns the finite continued fraction of p / q.
+    ///
+    /// Panics if q is zero.
+    ///
+    /// ```
+    /// use euler_library::continued_fractions as eu_cf;
+    ///
+    /// let cf = eu_cf::ContinuedFraction::rational(415, 93);
+    /// assert_eq!(cf.pre_period, [4, 2, 6, 7]);
+    /// assert!(cf.period.is_empty());
+    /// assert_eq!(cf.convergents().last(), Some((415, 93)));
+    /// ```
+    pub fn rational(mut p: u128, mut q: u128) -> ContinuedFraction {
+        assert!(q != 0, "zero denominator");
+        let mut terms = Vec::new();
+        while q != 0 {
+            terms.push((p / q) as usize);
+            let r = p % q;
+            p = q;
+            q = r;
+        }
+        ContinuedFraction { pre_period: terms, period: vec![], step: vec![] }
+    }
+
+    /// Returns the continued fraction of the positive quadratic irrational (a + b sqrt(n)) / c.
+    ///
+    /// The period is found from the first repeated complete quotient.
+    /// A perfect square n gives the rational expansion.
+    /// Panics if c is zero or the number is negative.
+    ///
+    /// ```
+    /// use euler_library::continued_fractions as eu_cf;
+    ///
+    /// // golden ratio (1 + sqrt(5)) / 2
+    /// let phi = eu_cf::ContinuedFraction::quadratic(1, 1, 5, 2);
+    /// assert_eq!((phi.pre_period, phi.period), (vec![], vec![1]));
+    ///
+    /// // (3 - sqrt(2)) / 7 = 1 / (3 + sqrt(2)) = [0; 4, 2, 2, 2, ...]
+    /// let x = eu_cf::ContinuedFraction::quadratic(3, -1, 2, 7);
+    /// assert_eq!((x.pre_period, x.period), (vec![0, 4], vec![2]));
+    ///
+    /// // (5 + 2 sqrt(3)) / 3 = [2; 1, 4, 1, 1, 2, 20, 2, 1, 1, ...]
+    /// let y = eu_cf::ContinuedFraction::quadratic(5, 2, 3, 3);
+    /// assert_eq!(y.quotients().take(10).collect::<Vec<_>>(), [2, 1, 4, 1, 1, 2, 20, 2, 1, 1]);
+    ///
+    /// // Problem 64: odd periods of sqrt(n) for n <= 13
+    /// let odd = (2..14)
+    ///     .map(|n| eu_cf::ContinuedFraction::quadratic(0, 1, n, 1))
+    ///     .filter(|cf| cf.period.len() % 2 == 1)
+    ///     .count();
+    /// assert_eq!(odd, 4);
+    /// ```
+    pub fn quadratic(a: i64, b: i64, n: usize, c: i64) -> ContinuedFraction {
+        assert!(c != 0, "zero denominator");
+        let (a, b, n, c) = (a as i128, b as i128, n as i128, c as i128);
+        let root_n = isqrt(n);
+        if root_n * root_n == n || b == 0 {
+            let (p, q) = (a + b * root_n, c);
+            let (p, q) = if q < 0 { (-p, -q) } else { (p, q) };
+            assert!(p >= 0, "negative number");
+            return ContinuedFraction::rational(p as u128, q as u128);
+        }
+
+        // (P + sqrt(D)) / Q with Q dividing D - P^2
+        let d = b * b * c * c * n;
+        let (mut p, mut q) = if b * c > 0 { (a * c, c * c) } else { (-a * c, -c * c) };
+        let root_d = isqrt(d);
+        assert!((q > 0) == (p + root_d >= 0), "negative number");
+
+        let mut seen = HashMap::new();
+        let mut terms = Vec::new();
+        while !seen.contains_key(&(p, q)) {
+            seen.insert((p, q), terms.len());
+            let t = if q > 0 { (p + root_d).div_euclid(q) } else { -(p + root_d).div_euclid(-q) - 1 };
+            terms.push(t as usize);
+            p = t * q - p;
+            q = (d - p * p) / q;
+        }
+        let start = seen[&(p, q)];
+        let period = terms.split_off(start);
+        ContinuedFraction { pre_period: terms, period, step: vec![] }
+    }
+
+    /// Returns the continued fraction of e = [2; 1, 2, 1, 1, 4, 1, 1, 6, ...].
+    ///
+    /// ```
+    /// use euler_library::continued_fractions as eu_cf;
+    ///
+    /// let e = eu_cf::ContinuedFraction::e();
+    /// assert_eq!(e.quotients().take(10).collect::<Vec<_>>(), [2, 1, 2, 1, 1, 4, 1, 1, 6, 1]);
+    /// assert_eq!(e.convergents().nth(9), Some((1457, 536)));
+    /// ```
+    pub fn e() -> ContinuedFraction {
+        ContinuedFraction { pre_period: vec![2], period: vec![1, 2, 1], step: vec![0, 2, 0] }
+    }
+
+    /// Returns an iterator of the partial quotients, endless unless the period is empty.
+    ///
+    /// ```
+    /// use euler_library::continued_fractions as eu_cf;
+    ///
+    /// let root_13 = eu_cf::ContinuedFraction::quadratic(0, 1, 13, 1);
+    /// assert_eq!(root_13.quotients().take(7).collect::<Vec<_>>(), [3, 1, 1, 1, 1, 6, 1]);
+    /// ```
+    pub fn quotients(&self) -> Quotients<'_> {
+        Quotients { cf: self, index: 0 }
+    }
+
+    /// Returns an iterator of the `u128` convergents, see `convergents`.
+    ///
+    /// ```
+    /// use euler_library::continued_fractions as eu_cf;
+    ///
+    /// let root_13 = eu_cf::ContinuedFraction::quadratic(0, 1, 13, 1);
+    /// assert_eq!(root_13.convergents().nth(15), Some((154451, 42837)));
+    /// ```
+    pub fn convergents(&self) -> Convergents<Quotients<'_>, Checked<u128>> {
+        convergents(self.quotients())
+    }
+
+    /// Returns an iterator of the `BigUint` convergents, see `big_convergents`.
+    ///
+    /// ```
+    /// use euler_library::continued_fractions as eu_cf;
+    ///
+    /// let root_23 = eu_cf::ContinuedFraction::quadratic(0, 1, 23, 1);
+    /// let (p, q) = root_23.big_convergents().nth(64).unwrap();
+    /// assert_eq!(p.to_string(), "3468077590434524694871282564");
+    /// assert_eq!(q.to_string(), "723144166673926627543073281");
+    /// ```
+    pub fn big_convergents(&self) -> Convergents<Quotients<'_>, Big> {
+        big_convergents(self.quotients())
+    }
+}
+
+/// Iterator of the partial quotients of a `ContinuedFraction`.
+pub struct Quotients<'a> {
+    cf: &'a ContinuedFraction,
+    index: usize,
+}
+
+impl<'a> Iterator for Quotients<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let cf = self.cf;
+        let i = self.index;
+        self.index += 1;
+        if i < cf.pre_period.len() {
+            return Some(cf.pre_period[i]);
+        }
+        if cf.period.is_empty() {
+            return None;
+        }
+        let (repeat, j) = ((i - cf.pre_period.len()) / cf.period.len(), (i - cf.pre_period.len()) % cf.period.len());
+        Some(cf.period[j] + repeat * cf.step.get(j).cloned().unwrap_or(0))
+    }
+}
+
+fn isqrt(n: i128) -> i128 {
+    let mut r = (n as f64).sqrt() as i128;
+    while r * r > n {
+        r -= 1;
+    }
+    while (r + 1) * (r + 1) <= n {
+        r += 1;
+    }
+    r
+}
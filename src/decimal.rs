@@ -0,0 +1,256 @@
+//! Arbitrary-precision fixed-point decimal numbers.
+//!
+//! A `BigDecimal` is a `BigInt` mantissa and a scale, the number of digits after the decimal point.
+//! Addition, subtraction and multiplication are exact, division is to a chosen scale.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::decimal::BigDecimal;
+//!
+//! let a: BigDecimal = "1.25".parse().unwrap();
+//! let b: BigDecimal = "-0.5".parse().unwrap();
+//!
+//! assert_eq!((a.clone() + b.clone()).to_string(), "0.75");
+//! assert_eq!((a.clone() * b.clone()).to_string(), "-0.625");
+//! assert_eq!(a.div(&b, 3).to_string(), "-2.500");
+//! assert_eq!(BigDecimal::from(1).div(&BigDecimal::from(7), 10).fraction_digits(), "1428571428");
+//! ```
+
+extern crate num;
+use self::num::{BigInt, BigUint, Signed, Zero, pow};
+use self::num::bigint::Sign;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+/// How digits past the requested precision are dropped.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Drop the digits, rounding towards zero.
+    Truncate,
+    /// Round to the nearest value, halves away from zero.
+    Nearest,
+}
+
+/// A decimal number, mantissa / 10^scale.
+#[derive(Clone, Debug)]
+pub struct BigDecimal {
+    mantissa: BigInt,
+    scale: usize,
+}
+
+fn ten_pow(n: usize) -> BigInt {
+    pow(BigInt::from(10), n)
+}
+
+impl BigDecimal {
+    /// Returns mantissa / 10^scale.
+    ///
+    /// ```
+    /// extern crate num;
+    /// extern crate euler_library;
+    /// use euler_library::decimal::BigDecimal;
+    ///
+    /// # fn main() {
+    /// assert_eq!(BigDecimal::new(num::BigInt::from(-31415), 4).to_string(), "-3.1415");
+    /// # }
+    /// ```
+    pub fn new(mantissa: BigInt, scale: usize) -> BigDecimal {
+        BigDecimal { mantissa, scale }
+    }
+
+    /// Returns the mantissa.
+    pub fn mantissa(&self) -> &BigInt {
+        &self.mantissa
+    }
+
+    /// Returns the number of digits after the decimal point.
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+
+    /// Returns the number with scale digits after the decimal point, truncating towards zero.
+    ///
+    /// ```
+    /// use euler_library::decimal::BigDecimal;
+    ///
+    /// let x: BigDecimal = "-2.71828".parse().unwrap();
+    /// assert_eq!(x.with_scale(2).to_string(), "-2.71");
+    /// assert_eq!(x.with_scale(7).to_string(), "-2.7182800");
+    /// ```
+    pub fn with_scale(&self, scale: usize) -> BigDecimal {
+        let mantissa = if scale >= self.scale {
+            &self.mantissa * ten_pow(scale - self.scale)
+        } else {
+            &self.mantissa / ten_pow(self.scale - scale)
+        };
+        BigDecimal { mantissa, scale }
+    }
+
+    /// Returns the integer part, truncating towards zero.
+    ///
+    /// ```
+    /// use euler_library::decimal::BigDecimal;
+    ///
+    /// let x: BigDecimal = "-12.9".parse().unwrap();
+    /// assert_eq!(x.integer_part().to_string(), "-12");
+    /// ```
+    pub fn integer_part(&self) -> BigInt {
+        &self.mantissa / ten_pow(self.scale)
+    }
+
+    /// Returns the scale digits after the decimal point.
+    ///
+    /// ```
+    /// use euler_library::decimal::BigDecimal;
+    ///
+    /// let x: BigDecimal = "-12.0350".parse().unwrap();
+    /// assert_eq!(x.fraction_digits(), "0350");
+    /// ```
+    pub fn fraction_digits(&self) -> String {
+        let digits = self.mantissa.abs().to_string();
+        if digits.len() >= self.scale {
+            digits[digits.len() - self.scale..].to_string()
+        } else {
+            "0".repeat(self.scale - digits.len()) + &digits
+        }
+    }
+
+    /// Returns self / other to scale digits after the decimal point, truncating towards zero.
+    ///
+    /// Panics if other is zero.
+    ///
+    /// ```
+    /// use euler_library::decimal::BigDecimal;
+    ///
+    /// let x: BigDecimal = "22".parse().unwrap();
+    /// let y: BigDecimal = "7.0".parse().unwrap();
+    /// assert_eq!(x.div(&y, 5).to_string(), "3.14285");
+    /// ```
+    pub fn div(&self, other: &BigDecimal, scale: usize) -> BigDecimal {
+        let numerator = &self.mantissa * ten_pow(other.scale + scale);
+        let denominator = &other.mantissa * ten_pow(self.scale);
+        BigDecimal { mantissa: numerator / denominator, scale }
+    }
+
+    // both mantissas at the larger scale
+    fn aligned(&self, other: &BigDecimal) -> (BigInt, BigInt, usize) {
+        let scale = self.scale.max(other.scale);
+        (self.with_scale(scale).mantissa, other.with_scale(scale).mantissa, scale)
+    }
+}
+
+impl From<BigInt> for BigDecimal {
+    fn from(n: BigInt) -> BigDecimal {
+        BigDecimal::new(n, 0)
+    }
+}
+
+impl From<BigUint> for BigDecimal {
+    fn from(n: BigUint) -> BigDecimal {
+        BigDecimal::new(BigInt::from_biguint(Sign::Plus, n), 0)
+    }
+}
+
+impl From<i64> for BigDecimal {
+    fn from(n: i64) -> BigDecimal {
+        BigDecimal::new(BigInt::from(n), 0)
+    }
+}
+
+impl FromStr for BigDecimal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<BigDecimal, String> {
+        let (int, frac) = match s.find('.') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => (s, ""),
+        };
+        if frac.starts_with(['+', '-']) {
+            return Err(format!("invalid decimal: {}", s));
+        }
+        let mantissa = (int.to_string() + frac).parse::<BigInt>().map_err(|_| format!("invalid decimal: {}", s))?;
+        Ok(BigDecimal::new(mantissa, frac.len()))
+    }
+}
+
+impl fmt::Display for BigDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.mantissa.is_negative() { "-" } else { "" };
+        let int = (self.mantissa.abs() / ten_pow(self.scale)).to_string();
+        if self.scale == 0 {
+            write!(f, "{}{}", sign, int)
+        } else {
+            write!(f, "{}{}.{}", sign, int, self.fraction_digits())
+        }
+    }
+}
+
+impl PartialEq for BigDecimal {
+    fn eq(&self, other: &BigDecimal) -> bool {
+        let (a, b, _) = self.aligned(other);
+        a == b
+    }
+}
+
+impl Eq for BigDecimal {}
+
+impl PartialOrd for BigDecimal {
+    fn partial_cmp(&self, other: &BigDecimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigDecimal {
+    fn cmp(&self, other: &BigDecimal) -> Ordering {
+        let (a, b, _) = self.aligned(other);
+        a.cmp(&b)
+    }
+}
+
+impl Zero for BigDecimal {
+    fn zero() -> BigDecimal {
+        BigDecimal::new(Zero::zero(), 0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.mantissa.is_zero()
+    }
+}
+
+impl Add for BigDecimal {
+    type Output = BigDecimal;
+
+    fn add(self, other: BigDecimal) -> BigDecimal {
+        let (a, b, scale) = self.aligned(&other);
+        BigDecimal::new(a + b, scale)
+    }
+}
+
+impl Sub for BigDecimal {
+    type Output = BigDecimal;
+
+    fn sub(self, other: BigDecimal) -> BigDecimal {
+        let (a, b, scale) = self.aligned(&other);
+        BigDecimal::new(a - b, scale)
+    }
+}
+
+impl Mul for BigDecimal {
+    type Output = BigDecimal;
+
+    fn mul(self, other: BigDecimal) -> BigDecimal {
+        BigDecimal::new(self.mantissa * other.mantissa, self.scale + other.scale)
+    }
+}
+
+impl Neg for BigDecimal {
+    type Output = BigDecimal;
+
+    fn neg(self) -> BigDecimal {
+        BigDecimal::new(-self.mantissa, self.scale)
+    }
+}
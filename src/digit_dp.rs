@@ -0,0 +1,194 @@
+//! Digit dynamic programming: counting and summing the numbers up to a bound with a digit property.
+//!
+//! The property is given as a state machine over the digits, most significant first: a start
+//! state, a transition taking a state and the next digit to a new state, or None to reject,
+//! and an acceptance predicate on the final state. Leading zeros are skipped, so the transition
+//! first sees the most significant digit. Numbers are counted from 1 up to the bound inclusive.
+//!
+//! Results for the states after a prefix are memoised, so the work is about the number of digits
+//! times the number of reachable states times the radix.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::digit_dp as eu_dp;
+//!
+//! // numbers up to 10^18 with digit sum 10
+//! let dp = eu_dp::digit_dp(10, 0, |&s: &u32, d| if s + d <= 10 { Some(s + d) } else { None }, |&s| s == 10);
+//! assert_eq!(dp.count(&1_000_000_000_000_000_000u128), 8_436_267);
+//! assert_eq!(dp.count(&1000u128), (1..1001u32).filter(|n| n.to_string().bytes().map(|b| (b - b'0') as u32).sum::<u32>() == 10).count() as u128);
+//!
+//! // numbers up to 10^6 with no two equal adjacent digits
+//! let dp = eu_dp::digit_dp(10, None, |&last: &Option<u32>, d| if last == Some(d) { None } else { Some(Some(d)) }, |_| true);
+//! assert_eq!(dp.count(&1_000_000u64), 597_870);
+//! ```
+
+extern crate num;
+use self::num::BigUint;
+
+use arith::{Arith, Big, Checked};
+use digits;
+use digits::DigitInt;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Numbers counts and sums can be accumulated in, with the arithmetic they are added in.
+pub trait DpInt: DigitInt {
+    type Arith: Arith<T = Self> + Default;
+}
+
+impl DpInt for u64 {
+    type Arith = Checked<u64>;
+}
+
+impl DpInt for u128 {
+    type Arith = Checked<u128>;
+}
+
+impl DpInt for BigUint {
+    type Arith = Big;
+}
+
+/// A digit property, given by a start state, a transition and an acceptance predicate.
+#[derive(Clone, Debug)]
+pub struct DigitDp<S, F, A> {
+    radix: u32,
+    start: S,
+    transition: F,
+    accept: A,
+}
+
+/// Returns the digit property in a radix with a start state, a transition and an acceptance predicate.
+///
+/// Panics if the radix is outside 2..=36.
+pub fn digit_dp<S, F, A>(radix: u32, start: S, transition: F, accept: A) -> DigitDp<S, F, A>
+    where S: Clone + Hash + Eq,
+          F: Fn(&S, u32) -> Option<S>,
+          A: Fn(&S) -> bool
+{
+    assert!((2..=36).contains(&radix), "radix must be in 2..=36, got {}", radix);
+    DigitDp { radix, start, transition, accept }
+}
+
+// the digits of a bound, the powers of the radix below its length, and the arithmetic
+struct Bound<T: DpInt> {
+    ar: T::Arith,
+    digits: Vec<u32>,
+    powers: Vec<T>,
+}
+
+impl<S, F, A> DigitDp<S, F, A>
+    where S: Clone + Hash + Eq,
+          F: Fn(&S, u32) -> Option<S>,
+          A: Fn(&S) -> bool
+{
+    /// Returns the number of n in 1..=bound with the property.
+    ///
+    /// Panics if the count overflows T.
+    ///
+    /// ```
+    /// use euler_library::digit_dp as eu_dp;
+    ///
+    /// // numbers whose digits never decrease
+    /// let dp = eu_dp::digit_dp(10, 0, |&last: &u32, d| if d >= last { Some(d) } else { None }, |_| true);
+    /// assert_eq!(dp.count(&99u64), 54);
+    /// assert_eq!(dp.count(&0u64), 0);
+    ///
+    /// // the sum of these would overflow a u64, the count does not
+    /// let dp = eu_dp::digit_dp(10, 0, |&s: &u32, d| if s + d <= 10 { Some(s + d) } else { None }, |&s| s == 10);
+    /// assert_eq!(dp.count(&1_000_000_000_000_000_000u64), 8_436_267);
+    /// ```
+    pub fn count<T: DpInt>(&self, bound: &T) -> T {
+        self.run(bound, false).0
+    }
+
+    /// Returns the sum of the n in 1..=bound with the property.
+    ///
+    /// Panics if the sum overflows T, so use a `BigUint` bound for large sums.
+    ///
+    /// ```
+    /// extern crate num;
+    /// extern crate euler_library;
+    /// use euler_library::digit_dp as eu_dp;
+    /// use num::BigUint;
+    ///
+    /// # fn main() {
+    /// // binary numbers with no two adjacent ones
+    /// let dp = eu_dp::digit_dp(2, 0, |&last: &u32, d| if last + d < 2 { Some(d) } else { None }, |_| true);
+    /// assert_eq!(dp.sum(&20u64), (1..21u64).filter(|n| n & (n >> 1) == 0).sum::<u64>());
+    ///
+    /// // every number up to 10^30 has a digit sum
+    /// let all = eu_dp::digit_dp(10, (), |_, _| Some(()), |_| true);
+    /// let bound = BigUint::from(10u32).pow(30);
+    /// assert_eq!(all.sum(&bound), &bound * (&bound + 1u32) / 2u32);
+    /// # }
+    /// ```
+    pub fn sum<T: DpInt>(&self, bound: &T) -> T {
+        self.run(bound, true).1
+    }
+
+    /// Returns the number and the sum of the n in 1..=bound with the property.
+    ///
+    /// Panics if the count or the sum overflows T.
+    pub fn count_sum<T: DpInt>(&self, bound: &T) -> (T, T) {
+        self.run(bound, true)
+    }
+
+    // count, and sum when sums is set, of the n in 1..=bound with the property
+    fn run<T: DpInt>(&self, bound: &T, sums: bool) -> (T, T) {
+        let ds = digits::digits(bound, self.radix).collect::<Vec<_>>();
+        let ar = T::Arith::default();
+        let mut powers = vec![ar.one()];
+        for _ in 1..ds.len() {
+            let p = powers.last().unwrap().mul_add_digit(self.radix, 0).unwrap();
+            powers.push(p);
+        }
+        powers.reverse();
+        let b = Bound { ar, digits: ds, powers };
+        self.walk(&b, 0, None, true, sums, &mut HashMap::new())
+    }
+
+    // count and sum of the completions of a prefix of pos digits, the suffixes valued alone,
+    // with the sum left at zero unless sums is set
+    fn walk<T: DpInt>(&self, b: &Bound<T>, pos: usize, state: Option<&S>, tight: bool, sums: bool,
+                      memo: &mut HashMap<(usize, S), (T, T)>)
+                      -> (T, T) {
+        if pos == b.digits.len() {
+            let accepted = state.is_some_and(|s| (self.accept)(s));
+            return (if accepted { b.ar.one() } else { b.ar.zero() }, b.ar.zero());
+        }
+        if let (Some(s), false) = (state, tight) {
+            if let Some(res) = memo.get(&(pos, s.clone())) {
+                return res.clone();
+            }
+        }
+
+        let (mut count, mut sum) = (b.ar.zero(), b.ar.zero());
+        let top = if tight { b.digits[pos] } else { self.radix - 1 };
+        for d in 0..top + 1 {
+            let next = if state.is_none() && d == 0 {
+                None
+            } else {
+                match (self.transition)(state.unwrap_or(&self.start), d) {
+                    Some(s) => Some(s),
+                    None => continue,
+                }
+            };
+            let (c, s) = self.walk(b, pos + 1, next.as_ref(), tight && d == top, sums, memo);
+            count = b.ar.add(&count, &c).expect("digit dp count overflows");
+            if sums {
+                let value = b.powers[pos]
+                    .mul_add_digit(d, 0)
+                    .and_then(|v| b.ar.mul(&v, &c))
+                    .and_then(|v| b.ar.add(&v, &s));
+                sum = value.and_then(|v| b.ar.add(&sum, &v)).expect("digit dp sum overflows");
+            }
+        }
+
+        if let (Some(s), false) = (state, tight) {
+            memo.insert((pos, s.clone()), (count.clone(), sum.clone()));
+        }
+        (count, sum)
+    }
+}
@@ -0,0 +1,428 @@
+//! Digits of unsigned integers in any radix from 2 to 36.
+//!
+//! Functions are generic over `DigitInt`, implemented for `u32`, `u64`, `u128`, `usize` and `BigUint`.
+//! Zero has the single digit 0. All functions panic if the radix is outside 2..=36.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::digits as eu_digits;
+//!
+//! assert_eq!(eu_digits::digits(&585u64, 2).collect::<Vec<_>>(), [1, 0, 0, 1, 0, 0, 1, 0, 0, 1]);
+//! assert_eq!(eu_digits::count(&585u64, 2), 10);
+//! assert_eq!(eu_digits::to_string(&255u64, 16), "ff");
+//!
+//! // Problem 36: numbers below one thousand palindromic in base 10 and base 2
+//! let sum: u64 = (1..1000u64)
+//!     .filter(|n| eu_digits::is_palindrome(n, 10) && eu_digits::is_palindrome(n, 2))
+//!     .sum();
+//! assert_eq!(sum, 1772);
+//! ```
+
+extern crate num;
+use self::num::{BigUint, Integer, ToPrimitive, Zero};
+
+/// Unsigned integers that can be split into digits.
+pub trait DigitInt: Clone {
+    fn zero() -> Self;
+    fn is_zero(&self) -> bool;
+    /// Returns (self / radix, self % radix).
+    fn div_rem_digit(&self, radix: u32) -> (Self, u32);
+    /// Returns self * radix + digit, or None on overflow.
+    fn mul_add_digit(&self, radix: u32, digit: u32) -> Option<Self>;
+}
+
+macro_rules! digit_int_impl {
+    ($($t:ty),*) => {$(
+        impl DigitInt for $t {
+            fn zero() -> $t {
+                0
+            }
+            fn is_zero(&self) -> bool {
+                *self == 0
+            }
+            fn div_rem_digit(&self, radix: u32) -> ($t, u32) {
+                (self / radix as $t, (self % radix as $t) as u32)
+            }
+            fn mul_add_digit(&self, radix: u32, digit: u32) -> Option<$t> {
+                self.checked_mul(radix as $t)?.checked_add(digit as $t)
+            }
+        }
+    )*};
+}
+
+digit_int_impl!(u32, u64, u128, usize);
+
+impl DigitInt for BigUint {
+    fn zero() -> BigUint {
+        Zero::zero()
+    }
+    fn is_zero(&self) -> bool {
+        Zero::is_zero(self)
+    }
+    fn div_rem_digit(&self, radix: u32) -> (BigUint, u32) {
+        let (q, r) = self.div_rem(&BigUint::from(radix));
+        (q, r.to_u32().unwrap())
+    }
+    fn mul_add_digit(&self, radix: u32, digit: u32) -> Option<BigUint> {
+        Some(self * radix + digit)
+    }
+}
+
+fn check_radix(radix: u32) {
+    assert!((2..=36).contains(&radix), "radix must be in 2..=36, got {}", radix);
+}
+
+/// Iterator of the digits of a number, least significant first.
+///
+/// Digits are produced one at a time by repeated division, without collecting them.
+/// Native integers do not allocate, a `BigUint` allocates a quotient per digit.
+#[derive(Clone, Debug)]
+pub struct DigitsRev<T> {
+    n: T,
+    radix: u32,
+    done: bool,
+}
+
+impl<T: DigitInt> Iterator for DigitsRev<T> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.done {
+            return None;
+        }
+        let (q, d) = self.n.div_rem_digit(self.radix);
+        self.done = q.is_zero();
+        self.n = q;
+        Some(d)
+    }
+}
+
+/// Returns an iterator of the digits of n in a radix, least significant first.
+///
+/// ```
+/// use euler_library::digits as eu_digits;
+///
+/// assert_eq!(eu_digits::digits_rev(&1234u32, 10).collect::<Vec<_>>(), [4, 3, 2, 1]);
+/// assert_eq!(eu_digits::digits_rev(&0u32, 10).collect::<Vec<_>>(), [0]);
+/// ```
+pub fn digits_rev<T: DigitInt>(n: &T, radix: u32) -> DigitsRev<T> {
+    check_radix(radix);
+    DigitsRev { n: n.clone(), radix, done: false }
+}
+
+/// Returns an iterator of the digits of n in a radix, most significant first.
+///
+/// The digits are collected into a vec first, and the iterator is double ended,
+/// so `.rev()` also gives the digits least significant first.
+///
+/// ```
+/// extern crate num;
+/// extern crate euler_library;
+/// use euler_library::digits as eu_digits;
+/// use num::BigUint;
+///
+/// # fn main() {
+/// assert_eq!(eu_digits::digits(&1234u64, 10).collect::<Vec<_>>(), [1, 2, 3, 4]);
+/// assert_eq!(eu_digits::digits(&35u64, 36).collect::<Vec<_>>(), [35]);
+///
+/// let big = BigUint::from(2u32).pow(100);
+/// assert_eq!(eu_digits::digits(&big, 10).take(5).collect::<Vec<_>>(), [1, 2, 6, 7, 6]);
+/// # }
+/// ```
+pub fn digits<T: DigitInt>(n: &T, radix: u32) -> ::std::vec::IntoIter<u32> {
+    let mut ds = digits_rev(n, radix).collect::<Vec<_>>();
+    ds.reverse();
+    ds.into_iter()
+}
+
+/// Returns the number with the given digits in a radix, most significant first,
+/// or None if it overflows.
+///
+/// Panics if a digit is not below the radix.
+///
+/// ```
+/// use euler_library::digits as eu_digits;
+///
+/// assert_eq!(eu_digits::from_digits::<u64>(&[1, 0, 1, 1], 2), Some(11));
+/// assert_eq!(eu_digits::from_digits::<u32>(&[], 10), Some(0));
+/// assert_eq!(eu_digits::from_digits::<u32>(&[5, 0, 0, 0, 0, 0, 0, 0, 0, 0], 10), None);
+/// ```
+pub fn from_digits<T: DigitInt>(ds: &[u32], radix: u32) -> Option<T> {
+    check_radix(radix);
+    ds.iter().try_fold(T::zero(), |acc, &d| {
+        assert!(d < radix, "digit {} out of range for radix {}", d, radix);
+        acc.mul_add_digit(radix, d)
+    })
+}
+
+/// Returns the number of digits of n in a radix.
+///
+/// ```
+/// use euler_library::digits as eu_digits;
+///
+/// assert_eq!(eu_digits::count(&0u64, 10), 1);
+/// assert_eq!(eu_digits::count(&u64::MAX, 2), 64);
+/// assert_eq!(eu_digits::count(&u128::MAX, 10), 39);
+/// ```
+pub fn count<T: DigitInt>(n: &T, radix: u32) -> usize {
+    digits_rev(n, radix).count()
+}
+
+/// Returns the sum of the digits of n in a radix.
+///
+/// ```
+/// extern crate num;
+/// extern crate euler_library;
+/// use euler_library::digits as eu_digits;
+/// use num::BigUint;
+///
+/// # fn main() {
+/// assert_eq!(eu_digits::sum(&0xffu64, 16), 30);
+///
+/// // Problem 16: sum of the digits of 2^1000
+/// assert_eq!(eu_digits::sum(&BigUint::from(2u32).pow(1000), 10), 1366);
+/// # }
+/// ```
+pub fn sum<T: DigitInt>(n: &T, radix: u32) -> u64 {
+    digits_rev(n, radix).map(u64::from).sum()
+}
+
+/// Returns the product of the digits of n in a radix.
+///
+/// The product never exceeds n, so it cannot overflow.
+///
+/// ```
+/// use euler_library::digits as eu_digits;
+///
+/// assert_eq!(eu_digits::product(&234u64, 10), 24);
+/// assert_eq!(eu_digits::product(&105u64, 10), 0);
+/// assert_eq!(eu_digits::product(&99_999_999_999_999_999_999u128, 10), 9u128.pow(20));
+/// ```
+pub fn product<T: DigitInt>(n: &T, radix: u32) -> T {
+    let one = T::zero().mul_add_digit(radix, 1).unwrap();
+    digits_rev(n, radix).fold(one, |acc, d| acc.mul_add_digit(d, 0).unwrap())
+}
+
+/// Returns n with its digits in a radix reversed, or None if it overflows.
+///
+/// Trailing zeros of n are dropped.
+///
+/// ```
+/// use euler_library::digits as eu_digits;
+///
+/// assert_eq!(eu_digits::reverse(&1230u64, 10), Some(321));
+/// assert_eq!(eu_digits::reverse(&0b1011u64, 2), Some(0b1101));
+/// assert_eq!(eu_digits::reverse(&4_000_000_009u32, 10), None);
+/// ```
+pub fn reverse<T: DigitInt>(n: &T, radix: u32) -> Option<T> {
+    digits_rev(n, radix).try_fold(T::zero(), |acc, d| acc.mul_add_digit(radix, d))
+}
+
+/// Returns true if the digits of n in a radix read the same backward or forward.
+///
+/// ```
+/// use euler_library::digits as eu_digits;
+///
+/// assert!(eu_digits::is_palindrome(&585u64, 10));
+/// assert!(eu_digits::is_palindrome(&585u64, 2));
+/// assert!(!eu_digits::is_palindrome(&10u64, 10));
+/// assert!(eu_digits::is_palindrome(&u64::MAX, 2));
+/// ```
+pub fn is_palindrome<T: DigitInt>(n: &T, radix: u32) -> bool {
+    let ds = digits_rev(n, radix).collect::<Vec<_>>();
+    ds.iter().eq(ds.iter().rev())
+}
+
+/// Returns n written in a radix, using the letters a to z for digits above 9.
+///
+/// ```
+/// use euler_library::digits as eu_digits;
+///
+/// assert_eq!(eu_digits::to_string(&0u64, 2), "0");
+/// assert_eq!(eu_digits::to_string(&35u64, 36), "z");
+/// assert_eq!(eu_digits::to_string(&u128::MAX, 16), "f".repeat(32));
+/// ```
+pub fn to_string<T: DigitInt>(n: &T, radix: u32) -> String {
+    digits(n, radix).map(|d| ::std::char::from_digit(d, radix).unwrap()).collect()
+}
+
+/// Iterator of palindromes in a radix, in increasing order.
+///
+/// Each palindrome is built by mirroring its first half, so no other numbers are visited.
+/// Iteration stops early once palindromes no longer fit a `u64`.
+#[derive(Clone, Debug)]
+pub struct Palindromes {
+    radix: u64,
+    len: u32,
+    last_len: u32,
+    half: u64,
+    half_end: u64,
+    bound: Option<u64>,
+    done: bool,
+}
+
+impl Palindromes {
+    fn new(radix: u32, len: u32, last_len: u32, bound: Option<u64>) -> Palindromes {
+        check_radix(radix);
+        let mut ps = Palindromes { radix: u64::from(radix), len, last_len, half: 0, half_end: 0, bound, done: false };
+        ps.start_len(len);
+        ps
+    }
+
+    // moves to the palindromes with len digits, ending iteration if even their first half
+    // does not fit a u64
+    fn start_len(&mut self, len: u32) {
+        let h = len.div_ceil(2);
+        self.len = len;
+        match if len == 1 { Some(0) } else { self.radix.checked_pow(h - 1) } {
+            Some(half) => self.half = half,
+            None => self.done = true,
+        }
+        self.half_end = self.radix.checked_pow(h).unwrap_or(u64::MAX);
+    }
+
+    // the palindrome with first half h, or None on overflow
+    fn mirror(&self, h: u64) -> Option<u64> {
+        let mut p = h;
+        let mut x = if self.len % 2 == 1 { h / self.radix } else { h };
+        while x > 0 {
+            p = p.checked_mul(self.radix)?.checked_add(x % self.radix)?;
+            x /= self.radix;
+        }
+        Some(p)
+    }
+}
+
+impl Iterator for Palindromes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while !self.done {
+            if self.half == self.half_end {
+                if self.len == self.last_len {
+                    self.done = true;
+                } else {
+                    let len = self.len + 1;
+                    self.start_len(len);
+                }
+                continue;
+            }
+            let p = self.mirror(self.half);
+            self.half += 1;
+            match p {
+                Some(p) if self.bound.is_none_or(|b| p < b) => return Some(p),
+                _ => self.done = true,
+            }
+        }
+        None
+    }
+}
+
+/// Returns an iterator of the palindromes with len digits in a radix, in increasing order.
+///
+/// The one digit palindromes include 0. Panics if len is 0.
+///
+/// ```
+/// use euler_library::digits as eu_digits;
+///
+/// assert_eq!(eu_digits::palindromes(1, 10).collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// assert_eq!(eu_digits::palindromes(3, 2).collect::<Vec<_>>(), [0b101, 0b111]);
+/// assert_eq!(eu_digits::palindromes(6, 10).count(), 900);
+///
+/// // palindromes stop at the largest u64
+/// assert_eq!(eu_digits::palindromes(20, 10).next(), Some(10_000_000_000_000_000_001));
+/// assert_eq!(eu_digits::palindromes(21, 10).next(), None);
+/// assert_eq!(eu_digits::palindromes(64, 2).next(), Some((1 << 63) + 1));
+/// assert_eq!(eu_digits::palindromes(65, 2).next(), None);
+/// assert_eq!(eu_digits::palindromes(1000, 10).next(), None);
+/// ```
+pub fn palindromes(len: u32, radix: u32) -> Palindromes {
+    assert!(len > 0, "palindromes need at least one digit");
+    Palindromes::new(radix, len, len, None)
+}
+
+/// Returns an iterator of the palindromes in a radix below bound, in increasing order.
+///
+/// ```
+/// use euler_library::digits as eu_digits;
+///
+/// assert_eq!(eu_digits::palindromes_below(30, 10).collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 11, 22]);
+/// assert_eq!(eu_digits::palindromes_below(0, 10).next(), None);
+///
+/// // Problem 36: numbers below one million palindromic in base 10 and base 2
+/// let sum: u64 = eu_digits::palindromes_below(1_000_000, 10)
+///     .filter(|n| eu_digits::is_palindrome(n, 2))
+///     .sum();
+/// assert_eq!(sum, 872_187);
+/// ```
+pub fn palindromes_below(bound: u64, radix: u32) -> Palindromes {
+    Palindromes::new(radix, 1, u32::MAX, Some(bound))
+}
+
+/// Iterator of the reverse-and-add sequence n + reverse(n), repeated, in a radix.
+///
+/// The first item is the first sum, not n itself. The sequence is infinite.
+#[derive(Clone, Debug)]
+pub struct ReverseAdd {
+    n: BigUint,
+    radix: u32,
+}
+
+impl Iterator for ReverseAdd {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<BigUint> {
+        let mut ds = self.n.to_radix_le(self.radix);
+        ds.reverse();
+        self.n += BigUint::from_radix_le(&ds, self.radix).unwrap();
+        Some(self.n.clone())
+    }
+}
+
+/// Returns the reverse-and-add sequence starting from n in a radix.
+///
+/// ```
+/// extern crate num;
+/// extern crate euler_library;
+/// use euler_library::digits as eu_digits;
+/// use num::BigUint;
+///
+/// # fn main() {
+/// let seq = eu_digits::reverse_add(&BigUint::from(349u32), 10).take(3).collect::<Vec<_>>();
+/// assert_eq!(seq, [BigUint::from(1292u32), BigUint::from(4213u32), BigUint::from(7337u32)]);
+/// # }
+/// ```
+pub fn reverse_add(n: &BigUint, radix: u32) -> ReverseAdd {
+    check_radix(radix);
+    ReverseAdd { n: n.clone(), radix }
+}
+
+/// Returns true if n is taken to be a Lychrel number in a radix, that is reverse-and-add
+/// reaches no palindrome within cap iterations.
+///
+/// ```
+/// extern crate num;
+/// extern crate euler_library;
+/// use euler_library::digits as eu_digits;
+/// use num::BigUint;
+///
+/// # fn main() {
+/// assert!(!eu_digits::is_lychrel(&BigUint::from(47u32), 10, 1));
+/// assert!(eu_digits::is_lychrel(&BigUint::from(196u32), 10, 50));
+///
+/// // 10677 first reaches a palindrome after 53 iterations
+/// assert!(eu_digits::is_lychrel(&BigUint::from(10677u32), 10, 52));
+/// assert!(!eu_digits::is_lychrel(&BigUint::from(10677u32), 10, 53));
+///
+/// // Problem 55: Lychrel numbers below ten thousand, with fifty iterations
+/// let count = (1..10_000u32).filter(|&n| eu_digits::is_lychrel(&BigUint::from(n), 10, 50)).count();
+/// assert_eq!(count, 249);
+/// # }
+/// ```
+pub fn is_lychrel(n: &BigUint, radix: u32, cap: usize) -> bool {
+    reverse_add(n, radix).take(cap).all(|m| {
+        let ds = m.to_radix_le(radix);
+        !ds.iter().eq(ds.iter().rev())
+    })
+}
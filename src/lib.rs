@@ -17,7 +17,21 @@
 //! extern crate euler_library;
 //! ```
 //!
+pub mod arith;
 pub mod big;
 pub mod cards;
+pub mod combinatorics;
 pub mod common;
+pub mod constants;
+pub mod continued_fractions;
+pub mod decimal;
+pub mod digit_dp;
+pub mod digits;
+pub mod outs;
+pub mod pandigital;
+pub mod partitions;
+pub mod pell;
 pub mod primes;
+pub mod ranges;
+pub mod rational;
+pub mod video_poker;
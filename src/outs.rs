@@ -0,0 +1,261 @@
+//! Outs and draw analysis for partial poker hands.
+//!
+//! Hand categories are numbered as in `cards::Hand::get_rank` divided by 1,000,000,
+//! from 0 for high card to 8 for a straight flush.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::outs as eu_outs;
+//! use euler_library::ranges::parse_card;
+//!
+//! let cards = |s: &str| s.split(' ').map(|c| parse_card(c).unwrap()).collect::<Vec<_>>();
+//!
+//! // open ended straight flush draw on the flop
+//! let outs = eu_outs::outs(&cards("8h 9h"), &cards("Th Jc 2h"), &[]);
+//! assert_eq!(outs.flush.len(), 9);
+//! assert_eq!(outs.straight.len(), 8);
+//! assert_eq!(outs.improving[0], (5, outs.flush.clone()));
+//!
+//! let mut draws = outs.flush.clone();
+//! draws.extend(outs.straight.iter().filter(|c| !outs.flush.contains(c)));
+//! assert_eq!(draws.len(), 15);
+//!
+//! let odds = eu_outs::hit_odds(draws.len(), 47);
+//! assert!((odds.either - 0.5412).abs() < 1e-4);
+//! ```
+
+use cards::{Card, SUITS, Suit, VALS, Val};
+
+/// Cards improving a hand, grouped by what they make.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outs {
+    /// Current hand category.
+    pub category: usize,
+    /// Cards making a flush the hand does not have.
+    pub flush: Vec<Card>,
+    /// Cards making a straight the hand does not have.
+    pub straight: Vec<Card>,
+    /// Cards making three of a kind from a pocket pair.
+    pub set: Vec<Card>,
+    /// Cards raising the hand category, by the category they make, best first.
+    pub improving: Vec<(usize, Vec<Card>)>,
+}
+
+impl Outs {
+    /// Returns the number of cards raising the hand category.
+    ///
+    /// ```
+    /// use euler_library::outs as eu_outs;
+    /// use euler_library::ranges::parse_card;
+    ///
+    /// let cards = |s: &str| s.split(' ').map(|c| parse_card(c).unwrap()).collect::<Vec<_>>();
+    /// assert_eq!(eu_outs::outs(&cards("Ah Kh"), &cards("2h 7h 9c"), &[]).count(), 9 + 14);
+    /// ```
+    pub fn count(&self) -> usize {
+        self.improving.iter().map(|(_, cs)| cs.len()).sum()
+    }
+}
+
+/// Chances of hitting an out.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Odds {
+    /// On the next card.
+    pub turn: f64,
+    /// On the card after, given the next card missed.
+    pub river: f64,
+    /// On either of the next two cards.
+    pub either: f64,
+}
+
+/// Returns the chances of hitting one of outs cards from unseen cards.
+///
+/// ```
+/// use euler_library::outs as eu_outs;
+///
+/// let odds = eu_outs::hit_odds(9, 47);
+/// assert!((odds.turn - 9.0 / 47.0).abs() < 1e-12);
+/// assert!((odds.river - 9.0 / 46.0).abs() < 1e-12);
+/// assert!((odds.either - 0.3497).abs() < 1e-4);
+/// ```
+pub fn hit_odds(outs: usize, unseen: usize) -> Odds {
+    let (o, u) = (outs as f64, unseen as f64);
+    let miss_both = if unseen > 1 { (u - o) / u * (u - 1.0 - o) / (u - 1.0) } else { 0.0 };
+    Odds {
+        turn: o / u,
+        river: if unseen > 1 { o / (u - 1.0) } else { 0.0 },
+        either: 1.0 - miss_both.max(0.0),
+    }
+}
+
+/// Returns the suit of five or more cards, if any.
+///
+/// Works on any number of cards.
+///
+/// ```
+/// use euler_library::outs as eu_outs;
+/// use euler_library::ranges::parse_card;
+///
+/// let cards = "2h 5h 9h Th Kh Ac".split(' ').map(|c| parse_card(c).unwrap()).collect::<Vec<_>>();
+/// assert_eq!(eu_outs::flush_suit(&cards), Some(euler_library::cards::Suit::Hearts));
+/// assert_eq!(eu_outs::flush_suit(&cards[1..]), None);
+/// ```
+pub fn flush_suit(cards: &[Card]) -> Option<Suit> {
+    SUITS.iter().cloned().find(|&s| cards.iter().filter(|c| c.suit == s).count() >= 5)
+}
+
+/// Returns the high value of the best straight in the cards, if any.
+///
+/// Works on any number of cards, an ace plays high or low.
+///
+/// ```
+/// use euler_library::cards::Val;
+/// use euler_library::outs as eu_outs;
+/// use euler_library::ranges::parse_card;
+///
+/// let cards = "Ah 2c 3d 4s 5h 9c".split(' ').map(|c| parse_card(c).unwrap()).collect::<Vec<_>>();
+/// assert_eq!(eu_outs::straight_high(&cards), Some(Val::Five));
+/// assert_eq!(eu_outs::straight_high(&cards[1..]), None);
+/// ```
+pub fn straight_high(cards: &[Card]) -> Option<Val> {
+    let has = |v: usize| cards.iter().any(|c| c.val as usize == v || (v == 1 && c.val == Val::Ace));
+    (5..15).rev().find(|&hi| (hi - 4..hi + 1).all(&has)).map(|hi| VALS[hi - 2])
+}
+
+/// Returns the suit missing one card of a flush, if any.
+///
+/// ```
+/// use euler_library::outs as eu_outs;
+/// use euler_library::ranges::parse_card;
+///
+/// let cards = "2h 5h 9h Kh Ac".split(' ').map(|c| parse_card(c).unwrap()).collect::<Vec<_>>();
+/// assert_eq!(eu_outs::flush_draw(&cards), Some(euler_library::cards::Suit::Hearts));
+/// ```
+pub fn flush_draw(cards: &[Card]) -> Option<Suit> {
+    if flush_suit(cards).is_some() {
+        return None;
+    }
+    SUITS.iter().cloned().find(|&s| cards.iter().filter(|c| c.suit == s).count() == 4)
+}
+
+/// Returns the values completing a straight the cards do not already make.
+///
+/// ```
+/// use euler_library::cards::Val;
+/// use euler_library::outs as eu_outs;
+/// use euler_library::ranges::parse_card;
+///
+/// let cards = "8h 9h Tc Jd 2s".split(' ').map(|c| parse_card(c).unwrap()).collect::<Vec<_>>();
+/// assert_eq!(eu_outs::straight_draw(&cards), [Val::Seven, Val::Queen]);
+///
+/// let gutshot = "8h 9h Jd Qs".split(' ').map(|c| parse_card(c).unwrap()).collect::<Vec<_>>();
+/// assert_eq!(eu_outs::straight_draw(&gutshot), [Val::Ten]);
+/// ```
+pub fn straight_draw(cards: &[Card]) -> Vec<Val> {
+    if straight_high(cards).is_some() {
+        return vec![];
+    }
+    VALS.iter()
+        .cloned()
+        .filter(|&val| {
+            let mut with = cards.to_vec();
+            with.push(Card { val, suit: Suit::Spades });
+            straight_high(&with).is_some()
+        })
+        .collect()
+}
+
+/// Returns the category of the best five card hand in the cards.
+///
+/// Works on any number of cards, categories needing five cards are only made with five or more.
+///
+/// ```
+/// use euler_library::outs as eu_outs;
+/// use euler_library::ranges::parse_card;
+///
+/// let cards = |s: &str| s.split(' ').map(|c| parse_card(c).unwrap()).collect::<Vec<_>>();
+/// assert_eq!(eu_outs::category(&cards("9h 9c 9d 4s")), 3);
+/// assert_eq!(eu_outs::category(&cards("9h 9c 9d 4s 4c Ks")), 6);
+/// assert_eq!(eu_outs::category(&cards("5h 6h 7h 8h 9h 9c")), 8);
+/// ```
+pub fn category(cards: &[Card]) -> usize {
+    let mut counts = VALS.iter()
+        .map(|&v| cards.iter().filter(|c| c.val == v).count())
+        .collect::<Vec<_>>();
+    counts.sort_by(|a, b| b.cmp(a));
+    let straight_flush = SUITS.iter().any(|&s| {
+        let suited = cards.iter().filter(|c| c.suit == s).cloned().collect::<Vec<_>>();
+        straight_high(&suited).is_some()
+    });
+
+    if straight_flush {
+        8
+    } else if counts[0] >= 4 {
+        7
+    } else if counts[0] == 3 && counts[1] >= 2 {
+        6
+    } else if flush_suit(cards).is_some() {
+        5
+    } else if straight_high(cards).is_some() {
+        4
+    } else if counts[0] == 3 {
+        3
+    } else if counts[0] == 2 && counts[1] == 2 {
+        2
+    } else if counts[0] == 2 {
+        1
+    } else {
+        0
+    }
+}
+
+/// Returns the outs of hole cards on a board, ignoring the dead cards.
+///
+/// ```
+/// use euler_library::outs as eu_outs;
+/// use euler_library::ranges::parse_card;
+///
+/// let cards = |s: &str| s.split(' ').map(|c| parse_card(c).unwrap()).collect::<Vec<_>>();
+///
+/// let outs = eu_outs::outs(&cards("7c 7d"), &cards("Ah Kc 2s Js"), &cards("7h"));
+/// assert_eq!(outs.set, cards("7s"));
+/// assert_eq!(outs.improving[0], (3, cards("7s")));
+/// ```
+pub fn outs(hole: &[Card], board: &[Card], dead: &[Card]) -> Outs {
+    let mut hand = hole.to_vec();
+    hand.extend_from_slice(board);
+    let current = category(&hand);
+    let has_flush = flush_suit(&hand).is_some();
+    let has_straight = straight_high(&hand).is_some();
+    let pocket_pair = if hole.len() == 2 && hole[0].val == hole[1].val { Some(hole[0].val) } else { None };
+
+    let mut res = Outs { category: current, flush: vec![], straight: vec![], set: vec![], improving: vec![] };
+    for &val in VALS.iter().rev() {
+        for &suit in &SUITS {
+            let card = Card { val, suit };
+            if hand.contains(&card) || dead.contains(&card) {
+                continue;
+            }
+            hand.push(card);
+            if !has_flush && flush_suit(&hand).is_some() {
+                res.flush.push(card);
+            }
+            if !has_straight && straight_high(&hand).is_some() {
+                res.straight.push(card);
+            }
+            if pocket_pair == Some(val) && hand.iter().filter(|c| c.val == val).count() == 3 {
+                res.set.push(card);
+            }
+            let made = category(&hand);
+            if made > current {
+                match res.improving.iter_mut().find(|(cat, _)| *cat == made) {
+                    Some((_, cs)) => cs.push(card),
+                    None => res.improving.push((made, vec![card])),
+                }
+            }
+            hand.pop();
+        }
+    }
+    res.improving.sort_by_key(|&(cat, _)| std::cmp::Reverse(cat));
+    res
+}
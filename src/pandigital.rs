@@ -0,0 +1,289 @@
+//! Pandigital numbers: numbers using each digit of a range lo..=hi exactly once, in base 10.
+//!
+//! Leading zeros are not allowed, so the 0 to 9 pandigitals have ten digits.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::pandigital as eu_pan;
+//!
+//! assert!(eu_pan::is_pandigital(3_214, 1, 4));
+//! assert!(!eu_pan::is_pandigital(3_314, 1, 4));
+//! assert_eq!(eu_pan::pandigitals(1, 3).collect::<Vec<_>>(), [123, 132, 213, 231, 312, 321]);
+//!
+//! // Problem 41: the largest pandigital prime, only 4 and 7 digits have a digit sum not divisible by 3
+//! let p = eu_pan::pandigitals(1, 7).descending().find(|&n| (2..).take_while(|d| d * d <= n).all(|d| n % d != 0));
+//! assert_eq!(p, Some(7_652_413));
+//!
+//! // Problem 43: sum of the 0 to 9 pandigitals with the prime substring divisibility property
+//! let ps = eu_pan::pandigitals(0, 9).with_filters(&eu_pan::prime_substrings());
+//! assert_eq!(ps.sum::<u64>(), 16_695_334_890);
+//! ```
+
+use digits;
+
+use std::cmp::Ordering;
+
+fn check_range(lo: u32, hi: u32) {
+    assert!(lo <= hi && hi <= 9, "digit range must be within 0..=9, got {}..={}", lo, hi);
+}
+
+/// Returns true if the digits ds use each digit of lo..=hi exactly once.
+///
+/// ```
+/// use euler_library::pandigital as eu_pan;
+///
+/// assert!(eu_pan::is_pandigital_digits(&[3, 0, 2, 1], 0, 3));
+/// assert!(!eu_pan::is_pandigital_digits(&[3, 0, 2, 2], 0, 3));
+/// assert!(!eu_pan::is_pandigital_digits(&[3, 0, 2, 1, 4], 0, 3));
+/// ```
+pub fn is_pandigital_digits(ds: &[u32], lo: u32, hi: u32) -> bool {
+    check_range(lo, hi);
+    if ds.len() != (hi - lo + 1) as usize {
+        return false;
+    }
+    let mut seen = 0u32;
+    for &d in ds {
+        if d < lo || d > hi || seen & (1 << d) != 0 {
+            return false;
+        }
+        seen |= 1 << d;
+    }
+    true
+}
+
+/// Returns true if n uses each digit of lo..=hi exactly once.
+///
+/// ```
+/// use euler_library::pandigital as eu_pan;
+///
+/// assert!(eu_pan::is_pandigital(192_384_576, 1, 9));
+/// assert!(eu_pan::is_pandigital(1_406_357_289, 0, 9));
+/// assert!(!eu_pan::is_pandigital(112_345_678, 1, 9));
+/// assert!(!eu_pan::is_pandigital(123_456_789, 0, 9));
+/// assert!(eu_pan::is_pandigital(0, 0, 0));
+/// ```
+pub fn is_pandigital(n: u64, lo: u32, hi: u32) -> bool {
+    is_pandigital_digits(&digits::digits(&n, 10).collect::<Vec<_>>(), lo, hi)
+}
+
+/// Returns the concatenation of n * 1, n * 2, ..., n * k, or None if it overflows a `u64`.
+///
+/// ```
+/// use euler_library::pandigital as eu_pan;
+///
+/// assert_eq!(eu_pan::concatenated_product(192, 3), Some(192_384_576));
+/// assert_eq!(eu_pan::concatenated_product(9, 5), Some(918_273_645));
+/// assert_eq!(eu_pan::concatenated_product(99_999, 5), None);
+/// ```
+pub fn concatenated_product(n: u64, k: u64) -> Option<u64> {
+    (1..k + 1).try_fold(0u64, |acc, i| {
+        let m = n.checked_mul(i)?;
+        let shift = 10u64.checked_pow(digits::count(&m, 10) as u32)?;
+        acc.checked_mul(shift)?.checked_add(m)
+    })
+}
+
+/// Returns the concatenated product of n with 1, 2, ..., k, for some k > 1, that is
+/// pandigital in lo..=hi, if there is one.
+///
+/// ```
+/// use euler_library::pandigital as eu_pan;
+///
+/// assert_eq!(eu_pan::concatenated_pandigital(192, 1, 9), Some(192_384_576));
+/// assert_eq!(eu_pan::concatenated_pandigital(193, 1, 9), None);
+///
+/// // Problem 38: the largest 1 to 9 pandigital concatenated product
+/// let best = (1..10_000).filter_map(|n| eu_pan::concatenated_pandigital(n, 1, 9)).max();
+/// assert_eq!(best, Some(932_718_654));
+/// ```
+pub fn concatenated_pandigital(n: u64, lo: u32, hi: u32) -> Option<u64> {
+    check_range(lo, hi);
+    let width = (hi - lo + 1) as usize;
+    let (mut c, mut len, mut k) = (0u64, 0, 0);
+    while len < width {
+        k += 1;
+        let m = n.checked_mul(k)?;
+        let m_len = digits::count(&m, 10);
+        c = c.checked_mul(10u64.checked_pow(m_len as u32)?)?.checked_add(m)?;
+        len += m_len;
+    }
+    if k > 1 && is_pandigital(c, lo, hi) { Some(c) } else { None }
+}
+
+/// A divisibility property of a substring of the digits, d_start to d_(start + len - 1),
+/// counted from 0 at the most significant digit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubstringDivisible {
+    start: usize,
+    len: usize,
+    divisor: u64,
+}
+
+/// Returns the property that the len digits from start make a multiple of divisor.
+///
+/// Panics if len or divisor is 0.
+///
+/// ```
+/// use euler_library::pandigital as eu_pan;
+///
+/// let f = eu_pan::substring_divisible(1, 3, 2);
+/// assert!(f.matches(1_406_357_289));
+/// assert!(!f.matches(1_407_356_289));
+/// assert!(!f.matches(14));
+/// ```
+pub fn substring_divisible(start: usize, len: usize, divisor: u64) -> SubstringDivisible {
+    assert!(len > 0 && divisor > 0, "substring length and divisor must be positive");
+    SubstringDivisible { start, len, divisor }
+}
+
+/// Returns the properties of Problem 43: d_2 d_3 d_4 is a multiple of 2, d_3 d_4 d_5 of 3,
+/// and so on up to d_8 d_9 d_10 of 17, counting the digits from 1.
+///
+/// ```
+/// use euler_library::pandigital as eu_pan;
+///
+/// assert!(eu_pan::prime_substrings().iter().all(|f| f.matches(1_406_357_289)));
+/// ```
+pub fn prime_substrings() -> Vec<SubstringDivisible> {
+    [2, 3, 5, 7, 11, 13, 17]
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| substring_divisible(i + 1, 3, p))
+        .collect()
+}
+
+impl SubstringDivisible {
+    /// Returns true if n has the property. Numbers too short to have the substring do not.
+    pub fn matches(&self, n: u64) -> bool {
+        let ds = digits::digits(&n, 10).collect::<Vec<_>>();
+        ds.len() >= self.start + self.len && self.holds(&ds)
+    }
+
+    // true if the digits ds, long enough to hold the substring, have the property
+    fn holds(&self, ds: &[u32]) -> bool {
+        let sub = ds[self.start..self.start + self.len]
+            .iter()
+            .fold(0, |acc, &d| acc * 10 + u64::from(d));
+        sub.is_multiple_of(self.divisor)
+    }
+}
+
+/// Iterator of the pandigital numbers in lo..=hi, in increasing or decreasing order.
+///
+/// Numbers are built digit by digit, and prefixes failing a filter are abandoned,
+/// so filtered searches visit far fewer than all (hi - lo + 1)! numbers.
+#[derive(Clone, Debug)]
+pub struct Pandigitals {
+    digits: Vec<u32>,
+    used: Vec<bool>,
+    path: Vec<usize>,
+    path_digits: Vec<u32>,
+    from: usize,
+    filters: Vec<SubstringDivisible>,
+    done: bool,
+}
+
+/// Returns an iterator of the pandigital numbers in lo..=hi in increasing order.
+///
+/// ```
+/// use euler_library::pandigital as eu_pan;
+///
+/// assert_eq!(eu_pan::pandigitals(0, 2).collect::<Vec<_>>(), [102, 120, 201, 210]);
+/// assert_eq!(eu_pan::pandigitals(0, 0).collect::<Vec<_>>(), [0]);
+/// assert_eq!(eu_pan::pandigitals(1, 9).count(), 362_880);
+/// ```
+pub fn pandigitals(lo: u32, hi: u32) -> Pandigitals {
+    check_range(lo, hi);
+    let digits = (lo..hi + 1).collect::<Vec<_>>();
+    Pandigitals {
+        used: vec![false; digits.len()],
+        digits,
+        path: Vec::new(),
+        path_digits: Vec::new(),
+        from: 0,
+        filters: Vec::new(),
+        done: false,
+    }
+}
+
+impl Pandigitals {
+    /// Returns the iterator in decreasing order.
+    ///
+    /// ```
+    /// use euler_library::pandigital as eu_pan;
+    ///
+    /// assert_eq!(eu_pan::pandigitals(0, 2).descending().collect::<Vec<_>>(), [210, 201, 120, 102]);
+    /// ```
+    pub fn descending(mut self) -> Pandigitals {
+        self.digits.reverse();
+        self
+    }
+
+    /// Returns the iterator restricted to numbers with a substring property.
+    ///
+    /// ```
+    /// use euler_library::pandigital as eu_pan;
+    ///
+    /// let ps = eu_pan::pandigitals(1, 5).with_filter(eu_pan::substring_divisible(0, 2, 13));
+    /// assert_eq!(ps.collect::<Vec<_>>(), eu_pan::pandigitals(1, 5).filter(|&n| n / 1000 % 13 == 0).collect::<Vec<_>>());
+    /// ```
+    pub fn with_filter(mut self, filter: SubstringDivisible) -> Pandigitals {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Returns the iterator restricted to numbers with all of some substring properties.
+    pub fn with_filters(mut self, filters: &[SubstringDivisible]) -> Pandigitals {
+        self.filters.extend_from_slice(filters);
+        self
+    }
+
+    // true if no filter completed by the last digit fails, and none is too long for a full number
+    fn prefix_ok(&self) -> bool {
+        let ds = &self.path_digits;
+        self.filters.iter().all(|f| match (f.start + f.len).cmp(&ds.len()) {
+            Ordering::Equal => f.holds(ds),
+            Ordering::Greater => ds.len() < self.digits.len(),
+            Ordering::Less => true,
+        })
+    }
+
+    // removes the last digit, so the next one tried at its place follows it
+    fn backtrack(&mut self) {
+        let i = self.path.pop().unwrap();
+        self.path_digits.pop();
+        self.used[i] = false;
+        self.from = i + 1;
+    }
+}
+
+impl Iterator for Pandigitals {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let n = self.digits.len();
+        while !self.done {
+            let depth = self.path.len();
+            let leading_zero = |d: u32| depth == 0 && d == 0 && n > 1;
+            match (self.from..n).find(|&i| !self.used[i] && !leading_zero(self.digits[i])) {
+                Some(i) => {
+                    self.path.push(i);
+                    self.path_digits.push(self.digits[i]);
+                    self.used[i] = true;
+                    self.from = 0;
+                    if !self.prefix_ok() {
+                        self.backtrack();
+                    } else if self.path.len() == n {
+                        let value = self.path_digits.iter().fold(0, |acc, &d| acc * 10 + u64::from(d));
+                        self.backtrack();
+                        return Some(value);
+                    }
+                }
+                None if depth == 0 => self.done = true,
+                None => self.backtrack(),
+            }
+        }
+        None
+    }
+}
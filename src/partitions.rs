@@ -0,0 +1,392 @@
+//! Enumeration of integer partitions.
+//!
+//! Partitions are listed with their parts in decreasing order, and partitions of n
+//! in decreasing lexicographic order, from [n] down to [1, 1, ..., 1].
+//! See `big::integer_partitions` and friends for counting alone.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::partitions as eu_partitions;
+//!
+//! let ps = eu_partitions::partitions(5).collect::<Vec<_>>();
+//! assert_eq!(ps, [vec![5], vec![4, 1], vec![3, 2], vec![3, 1, 1], vec![2, 2, 1], vec![2, 1, 1, 1], vec![1, 1, 1, 1, 1]]);
+//!
+//! // partitions of 10 into three distinct parts
+//! let ps = eu_partitions::partitions(10).distinct().min_parts(3).max_parts(3).collect::<Vec<_>>();
+//! assert_eq!(ps, [vec![7, 2, 1], vec![6, 3, 1], vec![5, 4, 1], vec![5, 3, 2]]);
+//!
+//! // Problem 76: ways of writing one hundred as a sum of at least two positive integers
+//! assert_eq!(eu_partitions::partitions(100).min_parts(2).total(), 190_569_291);
+//! ```
+
+/// Iterator of the partitions of n meeting some constraints, in decreasing lexicographic order.
+///
+/// Only the current partition is kept, and `total`, `rank` and `unrank` keep O(n) counts.
+/// Counts must fit a `u128`, true for n up to about 1,400.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Partitions {
+    n: usize,
+    max_part: usize,
+    min_parts: usize,
+    max_parts: usize,
+    distinct: bool,
+    parts: Vec<usize>,
+    started: bool,
+}
+
+/// Returns an iterator of all partitions of n.
+///
+/// The constraints `max_part`, `min_parts`, `max_parts` and `distinct` may be added
+/// before iterating.
+///
+/// ```
+/// use euler_library::partitions as eu_partitions;
+///
+/// assert_eq!(eu_partitions::partitions(0).collect::<Vec<_>>(), [Vec::<usize>::new()]);
+/// assert_eq!(eu_partitions::partitions(60).count(), 966_467);
+/// ```
+pub fn partitions(n: usize) -> Partitions {
+    Partitions {
+        n,
+        max_part: n,
+        min_parts: 0,
+        max_parts: n,
+        distinct: false,
+        parts: Vec::new(),
+        started: false,
+    }
+}
+
+impl Partitions {
+    /// Returns the iterator restricted to parts no larger than k.
+    ///
+    /// ```
+    /// use euler_library::partitions as eu_partitions;
+    ///
+    /// let ps = eu_partitions::partitions(5).max_part(2).collect::<Vec<_>>();
+    /// assert_eq!(ps, [vec![2, 2, 1], vec![2, 1, 1, 1], vec![1, 1, 1, 1, 1]]);
+    /// ```
+    pub fn max_part(mut self, k: usize) -> Partitions {
+        self.max_part = k;
+        self
+    }
+
+    /// Returns the iterator restricted to partitions with at least k parts.
+    pub fn min_parts(mut self, k: usize) -> Partitions {
+        self.min_parts = k;
+        self
+    }
+
+    /// Returns the iterator restricted to partitions with at most k parts.
+    ///
+    /// ```
+    /// use euler_library::partitions as eu_partitions;
+    ///
+    /// let ps = eu_partitions::partitions(6).max_parts(2).collect::<Vec<_>>();
+    /// assert_eq!(ps, [vec![6], vec![5, 1], vec![4, 2], vec![3, 3]]);
+    /// ```
+    pub fn max_parts(mut self, k: usize) -> Partitions {
+        self.max_parts = k;
+        self
+    }
+
+    /// Returns the iterator restricted to partitions into distinct parts.
+    ///
+    /// ```
+    /// use euler_library::partitions as eu_partitions;
+    ///
+    /// let ps = eu_partitions::partitions(6).distinct().collect::<Vec<_>>();
+    /// assert_eq!(ps, [vec![6], vec![5, 1], vec![4, 2], vec![3, 2, 1]]);
+    /// ```
+    pub fn distinct(mut self) -> Partitions {
+        self.distinct = true;
+        self
+    }
+
+    // the largest part allowed after a part k
+    fn next_cap(&self, k: usize) -> usize {
+        if self.distinct { k - 1 } else { k }
+    }
+
+    // the allowed numbers of further parts after used parts
+    fn parts_left(&self, used: usize) -> Option<(usize, usize)> {
+        let hi = self.max_parts.checked_sub(used)?;
+        Some((self.min_parts.saturating_sub(used), hi))
+    }
+
+    // true if r splits into parts no larger than cap after used parts
+    fn feasible(&self, r: usize, cap: usize, used: usize) -> bool {
+        let (lo, hi) = match self.parts_left(used) {
+            Some(bounds) => bounds,
+            None => return false,
+        };
+        if r == 0 {
+            return lo == 0;
+        }
+        if cap == 0 {
+            return false;
+        }
+        if !self.distinct {
+            return r.div_ceil(cap).max(lo) <= r.min(hi);
+        }
+        // c distinct parts no larger than cap make every sum from 1 + ... + c to cap + ... + (cap - c + 1)
+        (lo..hi.min(cap) + 1)
+            .take_while(|&c| c * (c + 1) / 2 <= r)
+            .any(|c| r <= c * cap - c * (c.max(1) - 1) / 2)
+    }
+
+    // appends the largest parts summing to r, no larger than cap
+    fn fill(&mut self, mut r: usize, mut cap: usize) {
+        while r > 0 {
+            let used = self.parts.len() + 1;
+            let v = (1..cap.min(r) + 1)
+                .rev()
+                .find(|&v| self.feasible(r - v, self.next_cap(v), used))
+                .expect("infeasible partition");
+            self.parts.push(v);
+            r -= v;
+            cap = self.next_cap(v);
+        }
+    }
+
+    // the counts for unbounded completions, holding caps up to the first part
+    fn counts(&self) -> Counts {
+        let mut counts = Counts { cap: 0, distinct: self.distinct, all: vec![0; self.n + 1] };
+        counts.all[0] = 1;
+        for c in 1..self.max_part.min(self.n) + 1 {
+            counts.step(c, true);
+            counts.cap = c;
+        }
+        counts
+    }
+
+    // true if the part count constraints can bind a partition of r after used parts
+    fn bounded(&self, r: usize, used: usize) -> bool {
+        self.parts_left(used).is_none_or(|(lo, hi)| lo > 0 || hi < r)
+    }
+
+    // partitions of r into parts no larger than cap completing a partition with used parts,
+    // where cap never rises between unbounded calls on the same counts
+    fn completions(&self, r: usize, cap: usize, used: usize, counts: &mut Counts) -> u128 {
+        let (lo, hi) = match self.parts_left(used) {
+            Some((lo, hi)) if lo <= hi => (lo, hi),
+            _ => return 0,
+        };
+        if !self.bounded(r, used) {
+            counts.lower_to(cap);
+            return counts.all[r];
+        }
+        let fewer = if lo > 0 { self.boxed(r, cap, lo - 1) } else { 0 };
+        self.boxed(r, cap, hi) - fewer
+    }
+
+    // completions of r with their first part above v, where v is at most cap
+    fn above(&self, r: usize, cap: usize, v: usize, used: usize, counts: &mut Counts) -> u128 {
+        if self.bounded(r, used) {
+            return self.completions(r, cap, used, counts) - self.completions(r, v, used, counts);
+        }
+        (v + 1..cap.min(r) + 1)
+            .rev()
+            .map(|w| self.completions(r - w, self.next_cap(w), used + 1, counts))
+            .sum()
+    }
+
+    // partitions of r into at most j parts no larger than cap, from the Gaussian binomial
+    // generating function of the box, built one part count at a time up to degree r
+    fn boxed(&self, r: usize, cap: usize, j: usize) -> u128 {
+        let mut gf = vec![0; r + 1];
+        gf[0] = 1;
+        if !self.distinct {
+            // [cap + k, k] = [cap + k - 1, k - 1] (1 - q^(cap + k)) / (1 - q^k)
+            for k in 1..j.min(r) + 1 {
+                divide(&mut gf, k);
+                multiply(&mut gf, cap + k);
+            }
+            return gf[r];
+        }
+        // k distinct parts make q^(k (k + 1) / 2) [cap, k], and [cap, k] = [cap, k - 1] (1 - q^(cap - k + 1)) / (1 - q^k)
+        let mut total = gf[r];
+        for k in (1..j.min(cap) + 1).take_while(|&k| k * (k + 1) / 2 <= r) {
+            divide(&mut gf, k);
+            multiply(&mut gf, cap + 1 - k);
+            total += gf[r - k * (k + 1) / 2];
+        }
+        total
+    }
+
+    /// Returns the number of partitions meeting the constraints.
+    ///
+    /// ```
+    /// use euler_library::partitions as eu_partitions;
+    ///
+    /// assert_eq!(eu_partitions::partitions(100).total(), 190_569_292);
+    /// assert_eq!(eu_partitions::partitions(100).distinct().max_parts(5).total(), 32_123);
+    /// ```
+    pub fn total(&self) -> u128 {
+        self.completions(self.n, self.max_part, 0, &mut self.counts())
+    }
+
+    /// Returns the index of a partition in the iteration order, or None if the parts are not
+    /// a partition meeting the constraints.
+    ///
+    /// ```
+    /// use euler_library::partitions as eu_partitions;
+    ///
+    /// let ps = eu_partitions::partitions(8).max_parts(4);
+    /// assert_eq!(ps.rank(&[8]), Some(0));
+    /// assert_eq!(ps.rank(&[3, 3, 1, 1]), ps.clone().position(|p| p == [3, 3, 1, 1]).map(|i| i as u128));
+    /// assert_eq!(ps.rank(&[3, 1, 1, 1, 1, 1]), None);
+    /// assert_eq!(ps.rank(&[1, 3, 3, 1]), None);
+    /// ```
+    pub fn rank(&self, parts: &[usize]) -> Option<u128> {
+        let ordered = parts.windows(2).all(|w| if self.distinct { w[0] > w[1] } else { w[0] >= w[1] });
+        let count_ok = parts.len() >= self.min_parts && parts.len() <= self.max_parts;
+        if !ordered || !count_ok || parts.iter().sum::<usize>() != self.n || parts.contains(&0) {
+            return None;
+        }
+        if parts.first().is_some_and(|&p| p > self.max_part) {
+            return None;
+        }
+
+        let mut counts = self.counts();
+        let (mut rank, mut r, mut cap) = (0, self.n, self.max_part);
+        for (i, &part) in parts.iter().enumerate() {
+            rank += self.above(r, cap, part, i, &mut counts);
+            r -= part;
+            cap = self.next_cap(part);
+        }
+        Some(rank)
+    }
+
+    /// Returns the partition at an index in the iteration order, or None if the index is
+    /// past the last partition.
+    ///
+    /// ```
+    /// use euler_library::partitions as eu_partitions;
+    ///
+    /// let ps = eu_partitions::partitions(30).distinct();
+    /// assert_eq!(ps.unrank(0), Some(vec![30]));
+    /// assert_eq!(ps.unrank(100), ps.clone().nth(100));
+    /// assert_eq!(ps.unrank(ps.total()), None);
+    ///
+    /// let big = eu_partitions::partitions(1000);
+    /// let p = big.unrank(10u128.pow(30)).unwrap();
+    /// assert_eq!(big.rank(&p), Some(10u128.pow(30)));
+    /// ```
+    pub fn unrank(&self, mut index: u128) -> Option<Vec<usize>> {
+        let mut counts = self.counts();
+        if index >= self.completions(self.n, self.max_part, 0, &mut counts) {
+            return None;
+        }
+        let (mut parts, mut r, mut cap) = (Vec::new(), self.n, self.max_part);
+        while r > 0 {
+            let used = parts.len();
+            let mut v = cap.min(r);
+            if self.bounded(r, used) {
+                // fewer completions lie above larger first parts, so search for the first v
+                // with no more above it than the index
+                let mut lo = 1;
+                while lo < v {
+                    let mid = (lo + v) / 2;
+                    if self.above(r, cap, mid, used, &mut counts) <= index { v = mid } else { lo = mid + 1 }
+                }
+                index -= self.above(r, cap, v, used, &mut counts);
+            } else {
+                loop {
+                    let count = self.completions(r - v, self.next_cap(v), used + 1, &mut counts);
+                    if index < count {
+                        break;
+                    }
+                    index -= count;
+                    v -= 1;
+                }
+            }
+            parts.push(v);
+            r -= v;
+            cap = self.next_cap(v);
+        }
+        Some(parts)
+    }
+}
+
+// counts of the partitions of every r up to n into parts no larger than cap, updated in place
+// one cap at a time
+struct Counts {
+    cap: usize,
+    distinct: bool,
+    all: Vec<u128>,
+}
+
+impl Counts {
+    // moves from cap c - 1 up to c, or from c down to c - 1
+    fn step(&mut self, c: usize, up: bool) {
+        // read the counts for cap c at r - c when adding any number of parts c, or removing them,
+        // and those for cap c - 1 when adding or removing a single part c
+        let ascending = up != self.distinct;
+        let n = self.all.len() - 1;
+        for i in 0..(n + 1).saturating_sub(c) {
+            let r = if ascending { c + i } else { n - i };
+            self.all[r] = apply(self.all[r], self.all[r - c], up);
+        }
+    }
+
+    fn lower_to(&mut self, cap: usize) {
+        while self.cap > cap {
+            let c = self.cap;
+            self.step(c, false);
+            self.cap -= 1;
+        }
+    }
+}
+
+fn apply(count: u128, with_c: u128, up: bool) -> u128 {
+    if up {
+        count.checked_add(with_c).expect("partition count overflows u128")
+    } else {
+        count - with_c
+    }
+}
+
+// divides a generating function by 1 - q^k
+fn divide(gf: &mut [u128], k: usize) {
+    for i in k..gf.len() {
+        gf[i] = apply(gf[i], gf[i - k], true);
+    }
+}
+
+// multiplies a generating function by 1 - q^k
+fn multiply(gf: &mut [u128], k: usize) {
+    for i in (k..gf.len()).rev() {
+        gf[i] = apply(gf[i], gf[i - k], false);
+    }
+}
+
+impl Iterator for Partitions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if !self.started {
+            self.started = true;
+            if !self.feasible(self.n, self.max_part, 0) {
+                return None;
+            }
+            let (n, cap) = (self.n, self.max_part);
+            self.fill(n, cap);
+            return Some(self.parts.clone());
+        }
+        // lower the rightmost part that can be lowered, then refill the rest as high as possible
+        let mut r = 0;
+        while let Some(part) = self.parts.pop() {
+            r += part;
+            let used = self.parts.len() + 1;
+            if let Some(v) = (1..part).rev().find(|&v| self.feasible(r - v, self.next_cap(v), used)) {
+                self.parts.push(v);
+                let cap = self.next_cap(v);
+                self.fill(r - v, cap);
+                return Some(self.parts.clone());
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,222 @@
+//! Pell equations x^2 - D y^2 = 1, the negative Pell equation x^2 - D y^2 = -1
+//! and the generalized x^2 - D y^2 = N.
+//!
+//! Solutions come from the convergents of the continued fraction of sqrt(D),
+//! see `common::sqrt_terms`.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::pell as eu_pell;
+//!
+//! let (x, y) = eu_pell::fundamental(61).unwrap();
+//! assert_eq!((x.to_string(), y.to_string()), ("1766319049".to_string(), "226153980".to_string()));
+//!
+//! // Problem 66 for D <= 7: the largest minimal x is for D = 5
+//! let best = (2..8).filter_map(|d| eu_pell::fundamental(d).map(|(x, _)| (x, d))).max().unwrap();
+//! assert_eq!(best.1, 5);
+//! ```
+
+extern crate num;
+use self::num::{BigInt, BigUint, Signed, Zero};
+use self::num::bigint::{Sign, ToBigInt, ToBigUint};
+
+use big;
+use common;
+use continued_fractions;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::iter;
+
+// the convergent of sqrt(d) at index k(period length)
+fn convergent(d: usize, k: impl Fn(usize) -> usize) -> Option<(BigUint, BigUint)> {
+    let (a0, period) = common::sqrt_terms(d)?;
+    let r = period.len();
+    continued_fractions::big_convergents(iter::once(a0).chain(period.into_iter().cycle())).nth(k(r))
+}
+
+/// Returns the fundamental (smallest positive) solution of x^2 - D y^2 = 1.
+///
+/// Returns None if D is a perfect square.
+///
+/// ```
+/// use euler_library::pell as eu_pell;
+///
+/// let (x, y) = eu_pell::fundamental(13).unwrap();
+/// assert_eq!((x.to_string(), y.to_string()), ("649".to_string(), "180".to_string()));
+/// assert_eq!(eu_pell::fundamental(16), None);
+/// ```
+pub fn fundamental(d: usize) -> Option<(BigUint, BigUint)> {
+    convergent(d, |r| if r % 2 == 0 { r - 1 } else { 2 * r - 1 })
+}
+
+/// Returns the fundamental solution of x^2 - D y^2 = -1.
+///
+/// Returns None if there is no solution, when the period of sqrt(D) is even.
+///
+/// ```
+/// use euler_library::pell as eu_pell;
+///
+/// let (x, y) = eu_pell::negative_fundamental(13).unwrap();
+/// assert_eq!((x.to_string(), y.to_string()), ("18".to_string(), "5".to_string()));
+/// assert_eq!(eu_pell::negative_fundamental(3), None);
+/// ```
+pub fn negative_fundamental(d: usize) -> Option<(BigUint, BigUint)> {
+    match common::sqrt_terms(d) {
+        Some((_, ref period)) if period.len() % 2 == 1 => convergent(d, |r| r - 1),
+        _ => None,
+    }
+}
+
+/// Iterator of the positive solutions of a Pell equation in increasing order.
+pub struct Solutions {
+    d: BigUint,
+    x: BigUint,
+    y: BigUint,
+    step: (BigUint, BigUint),
+}
+
+impl Iterator for Solutions {
+    type Item = (BigUint, BigUint);
+
+    fn next(&mut self) -> Option<(BigUint, BigUint)> {
+        let (u, v) = &self.step;
+        let x = &self.x * u + &self.d * &self.y * v;
+        let y = &self.x * v + &self.y * u;
+        Some((std::mem::replace(&mut self.x, x), std::mem::replace(&mut self.y, y)))
+    }
+}
+
+/// Returns an iterator of all positive solutions of x^2 - D y^2 = 1.
+///
+/// Returns None if D is a perfect square.
+///
+/// ```
+/// use euler_library::pell as eu_pell;
+///
+/// let xs = eu_pell::solutions(2).unwrap().take(4).map(|(x, _)| x.to_string()).collect::<Vec<_>>();
+/// assert_eq!(xs, ["3", "17", "99", "577"]);
+/// ```
+pub fn solutions(d: usize) -> Option<Solutions> {
+    let (x, y) = fundamental(d)?;
+    Some(Solutions { d: d.to_biguint().unwrap(), step: (x.clone(), y.clone()), x, y })
+}
+
+/// Returns an iterator of all positive solutions of x^2 - D y^2 = -1.
+///
+/// Returns None if there are none.
+///
+/// ```
+/// use euler_library::pell as eu_pell;
+///
+/// let xs = eu_pell::negative_solutions(2).unwrap().take(4).map(|(x, _)| x.to_string()).collect::<Vec<_>>();
+/// assert_eq!(xs, ["1", "7", "41", "239"]);
+/// ```
+pub fn negative_solutions(d: usize) -> Option<Solutions> {
+    let (x, y) = negative_fundamental(d)?;
+    Some(Solutions { d: d.to_biguint().unwrap(), step: fundamental(d)?, x, y })
+}
+
+/// Returns the fundamental solutions of x^2 - D y^2 = N, one or two per class of solutions.
+///
+/// Every solution is ±(x + y sqrt(D)) (u + v sqrt(D))^k for a fundamental (x, y) and (u, v) the
+/// fundamental solution of x^2 - D y^2 = 1. Found by searching y up to Nagell's bounds.
+/// Returns an empty vector if D is a perfect square or N is zero.
+///
+/// ```
+/// use euler_library::pell as eu_pell;
+///
+/// let fs = eu_pell::generalized(2, 7).iter().map(|(x, y)| (x.to_string(), y.to_string())).collect::<Vec<_>>();
+/// assert_eq!(fs, [("3".to_string(), "1".to_string()), ("-3".to_string(), "1".to_string())]);
+///
+/// assert!(eu_pell::generalized(3, -1).is_empty());
+/// ```
+pub fn generalized(d: usize, n: i64) -> Vec<(BigInt, BigInt)> {
+    let (u, v) = match fundamental(d) {
+        Some(uv) if n != 0 => uv,
+        _ => return vec![],
+    };
+    let abs_n = n.unsigned_abs().to_biguint().unwrap();
+    let d_big = d.to_biguint().unwrap();
+    let two = 2.to_biguint().unwrap();
+    let one = 1.to_biguint().unwrap();
+    let (lo, hi) = if n > 0 {
+        (Zero::zero(), big::isqrt(&(&v * &v * &abs_n / (&two * (&u + &one)))))
+    } else {
+        let lo = big::isqrt(&((&abs_n + &d_big - &one) / &d_big));
+        (lo, big::isqrt(&(&v * &v * &abs_n / (&two * (&u - &one)))))
+    };
+
+    let mut res = Vec::new();
+    let mut y = lo;
+    while y <= hi {
+        let t = BigInt::from(n) + BigInt::from_biguint(Sign::Plus, &d_big * &y * &y);
+        if !t.is_negative() {
+            let x = big::isqrt(&t.to_biguint().unwrap());
+            if BigInt::from_biguint(Sign::Plus, &x * &x) == t {
+                let (x, y) = (x.to_bigint().unwrap(), y.to_bigint().unwrap());
+                if !x.is_zero() {
+                    res.push((-x.clone(), y.clone()));
+                }
+                res.push((x, y));
+            }
+        }
+        y += &one;
+    }
+    res.sort_by(|a, b| (&a.1, a.0.abs(), a.0.is_negative()).cmp(&(&b.1, b.0.abs(), b.0.is_negative())));
+    res
+}
+
+/// Iterator of the nonnegative solutions of x^2 - D y^2 = N in increasing order.
+pub struct GeneralizedSolutions {
+    d: BigInt,
+    unit: (BigInt, BigInt),
+    heap: BinaryHeap<Reverse<(BigInt, BigInt, usize)>>,
+    last: Option<(BigInt, BigInt)>,
+}
+
+impl Iterator for GeneralizedSolutions {
+    type Item = (BigInt, BigInt);
+
+    fn next(&mut self) -> Option<(BigInt, BigInt)> {
+        loop {
+            let Reverse((x, y, class)) = self.heap.pop()?;
+            let (u, v) = &self.unit;
+            let (nx, ny) = (&x * u + &self.d * &y * v, &x * v + &y * u);
+            let (nx, ny) = if nx.is_negative() && !ny.is_positive() { (-nx, -ny) } else { (nx, ny) };
+            self.heap.push(Reverse((nx, ny, class)));
+
+            let solution = Some((x.clone(), y.clone()));
+            if !x.is_negative() && !y.is_negative() && self.last != solution {
+                self.last = solution.clone();
+                return solution;
+            }
+        }
+    }
+}
+
+/// Returns an iterator of the nonnegative solutions of x^2 - D y^2 = N, smallest first.
+///
+/// Ends immediately if D is a perfect square, N is zero or there are no solutions.
+///
+/// ```
+/// use euler_library::pell as eu_pell;
+///
+/// let xs = eu_pell::generalized_solutions(2, 7).take(5).map(|(x, y)| format!("{} {}", x, y));
+/// assert_eq!(xs.collect::<Vec<_>>(), ["3 1", "5 3", "13 9", "27 19", "75 53"]);
+///
+/// let negative = eu_pell::generalized_solutions(13, -4).take(2).map(|(x, y)| format!("{} {}", x, y));
+/// assert_eq!(negative.collect::<Vec<_>>(), ["3 1", "36 10"]);
+/// ```
+pub fn generalized_solutions(d: usize, n: i64) -> GeneralizedSolutions {
+    let unit = fundamental(d).map_or((Zero::zero(), Zero::zero()), |(u, v)| {
+        (u.to_bigint().unwrap(), v.to_bigint().unwrap())
+    });
+    let heap = generalized(d, n)
+        .into_iter()
+        .enumerate()
+        .map(|(class, (x, y))| Reverse((x, y, class)))
+        .collect();
+    GeneralizedSolutions { d: d.to_bigint().unwrap(), unit, heap, last: None }
+}
+
@@ -8,10 +8,38 @@
 //!
 //! assert_eq!(eu_primes::prime_factors(84), [2, 2, 3, 7]);
 //! assert_eq!(eu_primes::prime_factors_unique(84), [2, 3, 7]);
+//! assert_eq!(eu_primes::sieve(20), [2, 3, 5, 7, 11, 13, 17, 19]);
 //!
 //! ```
 
 
+/// Returns a vector of the primes less than or equal to n.
+///
+/// Sieve of Eratosthenes.
+///
+/// ```
+/// use euler_library::primes as eu_primes;
+///
+/// assert_eq!(eu_primes::sieve(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+/// assert_eq!(eu_primes::sieve(1_000_000).len(), 78498);
+/// ```
+///
+pub fn sieve(n: usize) -> Vec<usize> {
+    let mut is_prime = vec![true; n + 1];
+    let mut i = 2;
+    while i * i <= n {
+        if is_prime[i] {
+            let mut j = i * i;
+            while j <= n {
+                is_prime[j] = false;
+                j += i
+            }
+        }
+        i += 1;
+    }
+    (2..n + 1).filter(|&i| is_prime[i]).collect()
+}
+
 /// Returns a vector of the prime factors n.
 ///
 /// ```
@@ -19,18 +47,22 @@
 ///
 /// assert_eq!(eu_primes::prime_factors(342), [2, 3, 3, 19]);
 /// assert_eq!(eu_primes::prime_factors(123), [3, 41]);
+/// assert_eq!(eu_primes::prime_factors(1_000_000_007), [1_000_000_007]);
 /// ```
 ///
 pub fn prime_factors(mut n: usize) -> Vec<usize> {
     let mut xs: Vec<usize> = Vec::new();
     let mut i = 2;
-    while n > 1 {
-        while n % i == 0 {
+    while i <= n / i {
+        while n.is_multiple_of(i) {
             xs.push(i);
             n /= i;
         }
         i += 1;
     }
+    if n > 1 {
+        xs.push(n);
+    }
     xs
 }
 
@@ -60,7 +92,7 @@ pub fn prime_factors_unique(n: usize) -> Vec<usize> {
 /// ```
 ///
 pub fn sopf(n: usize) -> usize {
-    prime_factors_unique(n).iter().fold(0, |acc, x| acc + x)
+    prime_factors_unique(n).iter().sum()
 }
 
 
@@ -77,7 +109,7 @@ pub fn sopf(n: usize) -> usize {
 /// ```
 ///
 pub fn prime_factor_cnt(n: usize) -> Vec<usize> {
-    let mut s = vec![0 as usize; n];
+    let mut s = vec![0_usize; n];
     for (i, _) in s.clone().iter().enumerate().skip(2) {
         if s[i] == 0 {
             let mut j = i;
@@ -0,0 +1,238 @@
+//! Poker hand range notation for two card starting hands.
+//!
+//! A range is a comma separated list of terms:
+//!
+//! * pairs `QQ`, `QQ+` (queens or better) and `QQ-88`
+//! * suited, offsuit or any hands `AKs`, `AKo`, `AK`, the suffix in either case
+//! * kicker ranges `ATs+` (ATs up to AKs) and `A5s-A2s`
+//! * specific combos `AsKd`, suits may be upper or lower case
+//!
+//! Any term may end with a weight, `AKs:0.5`, which must be finite and not negative.
+//! The weight of a combo is taken from the last term naming it.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::ranges as eu_ranges;
+//!
+//! let range = eu_ranges::Range::parse("QQ+, AKs, A5s-A2s, KQo").unwrap();
+//! assert_eq!(range.combos.len(), 18 + 4 + 16 + 12);
+//!
+//! let dead = [eu_ranges::parse_card("Ah").unwrap()];
+//! assert_eq!(range.without_dead(&dead).combos.len(), 50 - 3 - 1 - 4);
+//! ```
+
+use cards::{Card, JOKER, SUITS, VALS, Val};
+use std::str::FromStr;
+
+pub use cards::parse_card;
+
+/// A two card starting hand, higher card first, with its weight in a range.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Combo {
+    pub cards: [Card; 2],
+    pub weight: f64,
+}
+
+/// A weighted set of two card starting hands.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Range {
+    pub combos: Vec<Combo>,
+}
+
+impl Range {
+    /// Returns the range described by a range string.
+    ///
+    /// ```
+    /// use euler_library::ranges as eu_ranges;
+    ///
+    /// let range = eu_ranges::Range::parse("TT-88, ATs+:0.5, AsKd").unwrap();
+    /// assert_eq!(range.combos.len(), 18 + 16 + 1);
+    /// assert_eq!(range.total_weight(), 18.0 + 8.0 + 1.0);
+    ///
+    /// assert_eq!(eu_ranges::Range::parse("AKS, QJO").unwrap().combos.len(), 4 + 12);
+    /// assert!(eu_ranges::Range::parse("AKx").is_err());
+    /// assert!(eu_ranges::Range::parse("AKs:-1").is_err());
+    /// assert!(eu_ranges::Range::parse("AKs:NaN").is_err());
+    /// assert!(eu_ranges::Range::parse("AKs:inf").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Range, String> {
+        let mut combos: Vec<Combo> = Vec::new();
+        for term in s.split(',').map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            let (hands, weight) = match term.find(':') {
+                Some(i) => {
+                    let weight = term[i + 1..].trim().parse::<f64>().map_err(|e| format!("{}: {}", term, e))?;
+                    if !weight.is_finite() || weight < 0.0 {
+                        return Err(format!("invalid weight: {}", term));
+                    }
+                    (term[..i].trim(), weight)
+                }
+                None => (term, 1.0),
+            };
+            for cards in parse_term(hands)? {
+                match combos.iter_mut().find(|c| c.cards == cards) {
+                    Some(c) => c.weight = weight,
+                    None => combos.push(Combo { cards, weight }),
+                }
+            }
+        }
+        Ok(Range { combos })
+    }
+
+    /// Returns the range without the combos holding any dead card.
+    ///
+    /// ```
+    /// use euler_library::ranges as eu_ranges;
+    ///
+    /// let range = eu_ranges::Range::parse("AA").unwrap();
+    /// let dead = [eu_ranges::parse_card("As").unwrap(), eu_ranges::parse_card("Kd").unwrap()];
+    /// assert_eq!(range.without_dead(&dead).combos.len(), 3);
+    /// ```
+    pub fn without_dead(&self, dead: &[Card]) -> Range {
+        let combos = self.combos
+            .iter()
+            .filter(|c| !c.cards.iter().any(|x| dead.contains(x)))
+            .cloned()
+            .collect();
+        Range { combos }
+    }
+
+    /// Returns the sum of the weights of the combos.
+    ///
+    /// ```
+    /// use euler_library::ranges as eu_ranges;
+    ///
+    /// assert_eq!(eu_ranges::Range::parse("KK:0.25, AKs").unwrap().total_weight(), 5.5);
+    /// ```
+    pub fn total_weight(&self) -> f64 {
+        self.combos.iter().map(|c| c.weight).sum()
+    }
+
+    /// Returns the combos as card vectors.
+    ///
+    /// ```
+    /// use euler_library::ranges as eu_ranges;
+    ///
+    /// let hands = eu_ranges::Range::parse("AsKs").unwrap().hands();
+    /// assert_eq!(hands[0][1], eu_ranges::parse_card("KS").unwrap());
+    /// ```
+    pub fn hands(&self) -> Vec<Vec<Card>> {
+        self.combos.iter().map(|c| c.cards.to_vec()).collect()
+    }
+}
+
+impl FromStr for Range {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Range, String> {
+        Range::parse(s)
+    }
+}
+
+/// Returns the range string parsed with the dead cards removed.
+///
+/// ```
+/// use euler_library::ranges as eu_ranges;
+///
+/// let dead = [eu_ranges::parse_card("Qc").unwrap()];
+/// assert_eq!(eu_ranges::parse_range("QQ+", &dead).unwrap().combos.len(), 15);
+/// assert!(eu_ranges::parse_range("QQ+, AsXX", &[]).is_err());
+/// ```
+pub fn parse_range(s: &str, dead: &[Card]) -> Result<Range, String> {
+    Ok(Range::parse(s)?.without_dead(dead))
+}
+
+fn parse_val(c: char) -> Result<Val, String> {
+    "23456789TJQKA"
+        .find(c.to_ascii_uppercase())
+        .map(|i| VALS[i])
+        .ok_or_else(|| format!("invalid card value: {}", c))
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Kind {
+    Pair,
+    Suited,
+    Offsuit,
+    Any,
+}
+
+// (high value, low value, kind) of a hand class such as "AKs"
+fn parse_class(s: &str) -> Result<(Val, Val, Kind), String> {
+    let cs = s.chars().collect::<Vec<_>>();
+    if cs.len() < 2 || cs.len() > 3 {
+        return Err(format!("invalid hand: {}", s));
+    }
+    let (a, b) = (parse_val(cs[0])?, parse_val(cs[1])?);
+    let kind = match (a == b, cs.get(2).map(|c| c.to_ascii_lowercase())) {
+        (true, None) => Kind::Pair,
+        (false, None) => Kind::Any,
+        (false, Some('s')) => Kind::Suited,
+        (false, Some('o')) => Kind::Offsuit,
+        _ => return Err(format!("invalid hand: {}", s)),
+    };
+    Ok((a.max(b), a.min(b), kind))
+}
+
+fn val_from(n: usize) -> Val {
+    VALS[n - 2]
+}
+
+fn combos(hi: Val, lo: Val, kind: Kind) -> Vec<[Card; 2]> {
+    let mut res = Vec::new();
+    for (i, &s1) in SUITS.iter().enumerate() {
+        for (j, &s2) in SUITS.iter().enumerate() {
+            let keep = match kind {
+                Kind::Pair => i > j,
+                Kind::Suited => i == j,
+                Kind::Offsuit => i != j,
+                Kind::Any => true,
+            };
+            if keep {
+                res.push([Card { val: hi, suit: s1 }, Card { val: lo, suit: s2 }]);
+            }
+        }
+    }
+    res
+}
+
+fn parse_term(s: &str) -> Result<Vec<[Card; 2]>, String> {
+    let cs = s.chars().collect::<Vec<_>>();
+    if cs.len() == 4 && !s.contains(&['-', '+'][..]) {
+        let (a, b) = (parse_card(&cs[..2].iter().collect::<String>())?,
+                      parse_card(&cs[2..].iter().collect::<String>())?);
+        if a == JOKER || b == JOKER {
+            return Err(format!("jokers are not allowed in ranges: {}", s));
+        }
+        if a == b {
+            return Err(format!("duplicate card: {}", s));
+        }
+        return Ok(vec![if a > b { [a, b] } else { [b, a] }]);
+    }
+
+    let classes = if let Some(head) = s.strip_suffix('+') {
+        let (hi, lo, kind) = parse_class(head)?;
+        if kind == Kind::Pair {
+            (hi as usize..Val::Ace as usize + 1).map(|v| (val_from(v), val_from(v), kind)).collect()
+        } else {
+            (lo as usize..hi as usize).map(|v| (hi, val_from(v), kind)).collect()
+        }
+    } else if let Some(i) = s.find('-') {
+        let (from, to) = (parse_class(&s[..i])?, parse_class(&s[i + 1..])?);
+        let ((hi1, lo1, kind), (hi2, lo2, kind2)) = (from, to);
+        if kind != kind2 {
+            return Err(format!("invalid range: {}", s));
+        }
+        let (lo, hi) = (lo1.min(lo2), lo1.max(lo2));
+        if kind == Kind::Pair {
+            (lo as usize..hi as usize + 1).map(|v| (val_from(v), val_from(v), kind)).collect()
+        } else if hi1 == hi2 {
+            (lo as usize..hi as usize + 1).map(|v| (hi1, val_from(v), kind)).collect()
+        } else {
+            return Err(format!("invalid range: {}", s));
+        }
+    } else {
+        vec![parse_class(s)?]
+    };
+    Ok(classes.into_iter().flat_map(|(hi, lo, kind)| combos(hi, lo, kind)).collect())
+}
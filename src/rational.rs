@@ -0,0 +1,319 @@
+//! Rational approximation, Farey sequences and the Stern–Brocot tree.
+//!
+//! Fractions are (numerator, denominator) pairs of `u128`, as in `continued_fractions`.
+//! Real numbers are given by their `ContinuedFraction`.
+//!
+//! # Examples
+//!
+//! ```
+//! use euler_library::rational as eu_rational;
+//!
+//! // Problem 71: the fraction left of 3/7 in the Farey sequence of order 8
+//! assert_eq!(eu_rational::farey_neighbours(3, 7, 8).0, (2, 5));
+//!
+//! // Problem 72: the number of reduced proper fractions with denominator <= 8
+//! assert_eq!(eu_rational::farey_length(8) - 2, 21);
+//!
+//! // Problem 73: the fractions between 1/3 and 1/2 with denominator <= 8
+//! let between = eu_rational::farey_from(1, 3, 8).skip(1).take_while(|&f| f != (1, 2));
+//! assert_eq!(between.collect::<Vec<_>>(), [(3, 8), (2, 5), (3, 7)]);
+//! ```
+
+use continued_fractions::ContinuedFraction;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+// The fractions closest to x from below and above with denominator at most n,
+// or when exact, the two Stern–Brocot parents of x, whose mediant is x.
+struct Bracket {
+    lower: (u128, u128),
+    upper: (u128, u128),
+    exact: bool,
+}
+
+// Walks down the Stern–Brocot tree towards the number with the given partial quotients,
+// taking whole runs of steps while the denominators stay within n.
+fn bracket<I: Iterator<Item = usize>>(quotients: I, n: u128) -> Bracket {
+    assert!(n > 0, "zero maximum denominator");
+    let mut bounds = [(0, 1), (1, 0)];
+    let mut quotients = quotients.peekable();
+    let mut i = 0;
+    while let Some(a) = quotients.next() {
+        let a = a as u128;
+        // even quotients move the lower bound up, odd ones the upper bound down
+        let (from, to) = (i % 2, 1 - i % 2);
+        let (f, t) = (bounds[from], bounds[to]);
+        let steps = (n - f.1).checked_div(t.1).map_or(a, |max| a.min(max));
+        if steps == a && quotients.peek().is_none() {
+            assert!(a > 0, "zero is not in the Stern-Brocot tree");
+            bounds[from] = (f.0 + (a - 1) * t.0, f.1 + (a - 1) * t.1);
+            return Bracket { lower: bounds[0], upper: bounds[1], exact: true };
+        }
+        bounds[from] = (f.0 + steps * t.0, f.1 + steps * t.1);
+        if steps < a {
+            return Bracket { lower: bounds[0], upper: bounds[1], exact: false };
+        }
+        i += 1;
+    }
+    panic!("empty continued fraction")
+}
+
+// Compares two numbers by their partial quotients. A finished expansion acts as an
+// infinite quotient, and differences at odd positions order the numbers the other way.
+fn compare<I, J>(xs: I, ys: J) -> Ordering
+    where I: IntoIterator<Item = usize>,
+          J: IntoIterator<Item = usize>
+{
+    let (mut xs, mut ys) = (xs.into_iter(), ys.into_iter());
+    let mut i = 0;
+    loop {
+        let ord = match (xs.next(), ys.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(x), Some(y)) => x.cmp(&y),
+        };
+        if ord != Ordering::Equal {
+            return if i % 2 == 0 { ord } else { ord.reverse() };
+        }
+        i += 1;
+    }
+}
+
+/// Returns the fraction closest to p / q with denominator at most n.
+///
+/// Ties go to the smaller denominator.
+/// Panics if q or n is zero.
+///
+/// ```
+/// use euler_library::rational as eu_rational;
+///
+/// assert_eq!(eu_rational::best_approximation(31_415_926_535, 10_000_000_000, 1000), (355, 113));
+/// assert_eq!(eu_rational::best_approximation(3, 7, 100), (3, 7));
+/// // 1/3 and 1/2 are both 1/12 from 5/12
+/// assert_eq!(eu_rational::best_approximation(5, 12, 3), (1, 2));
+/// ```
+pub fn best_approximation(p: u128, q: u128, n: u128) -> (u128, u128) {
+    if p == 0 {
+        assert!(q != 0 && n > 0, "zero denominator");
+        return (0, 1);
+    }
+    best_approximation_cf(&ContinuedFraction::rational(p, q), n)
+}
+
+/// Returns the fraction closest to a real number with denominator at most n.
+///
+/// The number is given by its continued fraction, which may be endless.
+/// Ties go to the smaller denominator. Panics if n is zero.
+///
+/// ```
+/// extern crate num;
+/// extern crate euler_library;
+/// use euler_library::constants as eu_constants;
+/// use euler_library::continued_fractions::ContinuedFraction;
+/// use euler_library::rational as eu_rational;
+/// use num::ToPrimitive;
+///
+/// # fn main() {
+/// let root_2 = ContinuedFraction::quadratic(0, 1, 2, 1);
+/// assert_eq!(eu_rational::best_approximation_cf(&root_2, 100), (140, 99));
+/// assert_eq!(eu_rational::best_approximation_cf(&ContinuedFraction::e(), 1000), (1457, 536));
+///
+/// // pi from 30 decimal places
+/// let pi = eu_constants::pi(30);
+/// let pi = ContinuedFraction::rational(pi.mantissa().to_u128().unwrap(), 10u128.pow(30));
+/// assert_eq!(eu_rational::best_approximation_cf(&pi, 30_000), (94_053, 29_938));
+/// # }
+/// ```
+pub fn best_approximation_cf(cf: &ContinuedFraction, n: u128) -> (u128, u128) {
+    let Bracket { lower, upper, exact } = bracket(cf.quotients(), n);
+    if exact {
+        return (lower.0 + upper.0, lower.1 + upper.1);
+    }
+    let mid = ContinuedFraction::rational(lower.0 * upper.1 + upper.0 * lower.1, 2 * lower.1 * upper.1);
+    match compare(cf.quotients(), mid.quotients()) {
+        Ordering::Less => lower,
+        Ordering::Greater => upper,
+        Ordering::Equal => if lower.1 <= upper.1 { lower } else { upper },
+    }
+}
+
+/// Returns the fractions with denominator at most n closest to p / q from below and above.
+///
+/// When q <= n these are the neighbours of p / q in the Farey sequence of order n.
+/// Fractions greater than one are allowed. Panics if p or q is zero.
+///
+/// ```
+/// use euler_library::rational as eu_rational;
+///
+/// assert_eq!(eu_rational::farey_neighbours(1, 2, 5), ((2, 5), (3, 5)));
+/// // Problem 71
+/// assert_eq!(eu_rational::farey_neighbours(3, 7, 1_000_000).0, (428_570, 999_997));
+/// // 1/3 < 0.35 < 2/5
+/// assert_eq!(eu_rational::farey_neighbours(35, 100, 5), ((1, 3), (2, 5)));
+/// ```
+pub fn farey_neighbours(p: u128, q: u128, n: u128) -> ((u128, u128), (u128, u128)) {
+    assert!(p != 0, "zero has no lower neighbour");
+    let Bracket { lower, upper, exact } = bracket(ContinuedFraction::rational(p, q).quotients(), n);
+    if !exact {
+        return (lower, upper);
+    }
+    // the closest descendants of x on each side are the parents plus multiples of x
+    let x = (lower.0 + upper.0, lower.1 + upper.1);
+    let toward_x = |f: (u128, u128)| {
+        let k = (n - f.1) / x.1;
+        (f.0 + k * x.0, f.1 + k * x.1)
+    };
+    (toward_x(lower), toward_x(upper))
+}
+
+/// Iterator of a Farey sequence in increasing order, ending at 1/1.
+pub struct Farey {
+    n: u128,
+    a: u128,
+    b: u128,
+    c: u128,
+    d: u128,
+    done: bool,
+}
+
+impl Iterator for Farey {
+    type Item = (u128, u128);
+
+    fn next(&mut self) -> Option<(u128, u128)> {
+        if self.done {
+            return None;
+        }
+        let current = (self.a, self.b);
+        if self.a >= self.b {
+            self.done = true;
+        } else {
+            let k = (self.n + self.b) / self.d;
+            let (c, d) = (k * self.c - self.a, k * self.d - self.b);
+            self.a = self.c;
+            self.b = self.d;
+            self.c = c;
+            self.d = d;
+        }
+        Some(current)
+    }
+}
+
+/// Returns an iterator of the Farey sequence of order n, the reduced fractions from 0/1 to 1/1
+/// with denominator at most n.
+///
+/// Only the current pair of terms is kept. Panics if n is zero.
+///
+/// ```
+/// use euler_library::rational as eu_rational;
+///
+/// let f5 = eu_rational::farey(5).collect::<Vec<_>>();
+/// assert_eq!(f5[..6], [(0, 1), (1, 5), (1, 4), (1, 3), (2, 5), (1, 2)]);
+/// assert_eq!(f5.len(), 11);
+/// ```
+pub fn farey(n: u128) -> Farey {
+    assert!(n > 0, "zero order");
+    Farey { n, a: 0, b: 1, c: 1, d: n, done: false }
+}
+
+/// Returns an iterator of the Farey sequence of order n starting at p / q.
+///
+/// Panics unless p / q is a reduced fraction from 0 to 1 with denominator at most n.
+///
+/// ```
+/// use euler_library::rational as eu_rational;
+///
+/// let tail = eu_rational::farey_from(3, 4, 5).collect::<Vec<_>>();
+/// assert_eq!(tail, [(3, 4), (4, 5), (1, 1)]);
+/// ```
+pub fn farey_from(p: u128, q: u128, n: u128) -> Farey {
+    assert!(p <= q && q <= n, "not in the Farey sequence");
+    if p == 0 {
+        return farey(n);
+    }
+    let (_, (c, d)) = farey_neighbours(p, q, n);
+    assert!(c * q - d * p == 1, "fraction is not reduced");
+    Farey { n, a: p, b: q, c, d, done: false }
+}
+
+// sum of phi(k) for k <= n, from sum over d of Phi(n / d) = n (n + 1) / 2
+fn totient_sum(n: u128, memo: &mut HashMap<u128, u128>) -> u128 {
+    if let Some(&s) = memo.get(&n) {
+        return s;
+    }
+    let mut s = n * (n + 1) / 2;
+    let mut d = 2;
+    while d <= n {
+        let m = n / d;
+        let last = n / m;
+        s -= (last - d + 1) * totient_sum(m, memo);
+        d = last + 1;
+    }
+    memo.insert(n, s);
+    s
+}
+
+/// Returns the number of terms in the Farey sequence of order n, counting 0/1 and 1/1.
+///
+/// Uses the totient summatory function in sublinear time, without listing the terms.
+///
+/// ```
+/// use euler_library::rational as eu_rational;
+///
+/// assert_eq!(eu_rational::farey_length(5), 11);
+/// // Problem 72
+/// assert_eq!(eu_rational::farey_length(1_000_000) - 2, 303_963_552_391);
+/// ```
+pub fn farey_length(n: u128) -> u128 {
+    1 + totient_sum(n, &mut HashMap::new())
+}
+
+/// Returns the path from 1/1 to p / q in the Stern–Brocot tree, as 'L' and 'R' moves.
+///
+/// Runs of moves are the partial quotients of p / q, so the path can be long.
+/// Panics if p or q is zero.
+///
+/// ```
+/// use euler_library::rational as eu_rational;
+///
+/// assert_eq!(eu_rational::stern_brocot_path(1, 1), "");
+/// assert_eq!(eu_rational::stern_brocot_path(3, 7), "LLRR");
+/// assert_eq!(eu_rational::stern_brocot_path(22, 6), "RRRLR");
+/// ```
+pub fn stern_brocot_path(p: u128, q: u128) -> String {
+    assert!(p != 0, "zero is not in the Stern-Brocot tree");
+    let quotients = ContinuedFraction::rational(p, q).pre_period;
+    let last = quotients.len() - 1;
+    let mut path = String::new();
+    for (i, &a) in quotients.iter().enumerate() {
+        let run = if i == last { a - 1 } else { a };
+        let step = if i % 2 == 0 { "R" } else { "L" };
+        path.push_str(&step.repeat(run));
+    }
+    path
+}
+
+/// Returns the fraction at the end of a Stern–Brocot path from 1/1.
+///
+/// ```
+/// use euler_library::rational as eu_rational;
+///
+/// assert_eq!(eu_rational::from_stern_brocot_path("LLRR"), Ok((3, 7)));
+/// assert_eq!(eu_rational::from_stern_brocot_path(""), Ok((1, 1)));
+/// assert!(eu_rational::from_stern_brocot_path("LRX").is_err());
+///
+/// let path = eu_rational::stern_brocot_path(355, 113);
+/// assert_eq!(eu_rational::from_stern_brocot_path(&path), Ok((355, 113)));
+/// ```
+pub fn from_stern_brocot_path(path: &str) -> Result<(u128, u128), String> {
+    let (mut lower, mut upper) = ((0, 1), (1, 0));
+    for c in path.chars() {
+        let mediant = (lower.0 + upper.0, lower.1 + upper.1);
+        match c {
+            'L' => upper = mediant,
+            'R' => lower = mediant,
+            _ => return Err(format!("invalid Stern-Brocot move: {}", c)),
+        }
+    }
+    Ok((lower.0 + upper.0, lower.1 + upper.1))
+}